@@ -0,0 +1,348 @@
+//! Running many jobs, with a bounded number in flight at once.
+//!
+//! A [`Batch`] takes an iterator of specs (anything describing a conversion, such as a file name),
+//! and a function which creates a [`job::Create`] call for each spec. The jobs are submitted at
+//! most [`Batch::max_in_flight`] at a time, and no faster than [`Batch::rate_limit`] allows. Each
+//! job is tracked until it finishes or fails, either by polling it, or using webhooks (see
+//! [`Tracking`]), and the results are yielded as a stream of `(spec, result)` pairs, in the order
+//! the jobs complete:
+//!
+//! ```no_run
+//! # async fn example(client: cloudconvert::HyperClient) {
+//! use cloudconvert::batch::Batch;
+//! use cloudconvert::task::ImportUrl;
+//! use cloudconvert::{Format, ImportConvertExport};
+//! use futures_util::StreamExt;
+//!
+//! let urls = ["https://example.com/a.docx", "https://example.com/b.docx"];
+//! let batch = Batch {
+//!     max_in_flight: 10,
+//!     ..Batch::default()
+//! };
+//! let mut results = batch.run(&client, urls, |url| {
+//!     ImportConvertExport {
+//!         tag: Some("nightly".into()),
+//!         import: ImportUrl {
+//!             url: url.to_string().into(),
+//!             filename: None,
+//!             headers: None,
+//!             extra: Default::default(),
+//!         },
+//!         input_format: Format::Docx,
+//!         output_format: Format::Pdf,
+//!         export_inline: false,
+//!         timeout: None,
+//!         webhook_url: None,
+//!     }
+//!     .into()
+//! });
+//! while let Some((url, result)) = results.next().await {
+//!     println!("{url}: {:?}", result.map(|job| job.status));
+//! }
+//! # }
+//! ```
+//!
+//! API calls which fail because the account's rate or concurrency limits have been exceeded (with
+//! a `429 Too Many Requests` response) are retried, up to [`Batch::max_retries`] times.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures_util::{Stream, StreamExt};
+use tokio::sync::oneshot;
+use tokio::time::Instant;
+
+use crate::job::{self, Job};
+use crate::webhook::{Event, EventKind};
+use crate::{Client, Error, Status};
+
+/// Options for running a batch of jobs. See the [module docs](self).
+#[derive(Debug)]
+pub struct Batch {
+    /// The maximum number of jobs which are submitted, but haven't finished or failed, at once.
+    ///
+    /// This should be no more than the number of concurrent jobs allowed by the account.
+    pub max_in_flight: usize,
+
+    /// The minimum time between submitting jobs, if any.
+    pub rate_limit: Option<Duration>,
+
+    /// How the status of each job is tracked.
+    pub tracking: Tracking,
+
+    /// The number of times a rate limited API call is retried.
+    pub max_retries: u32,
+
+    /// The delay before retrying a rate limited API call. This doubles after each retry.
+    pub retry_delay: Duration,
+}
+
+impl Default for Batch {
+    fn default() -> Batch {
+        Batch {
+            max_in_flight: 5,
+            rate_limit: None,
+            tracking: Tracking::Poll {
+                interval: Duration::from_secs(5),
+            },
+            max_retries: 5,
+            retry_delay: Duration::from_secs(1),
+        }
+    }
+}
+
+/// How a [`Batch`] tracks the status of its jobs.
+#[derive(Debug)]
+pub enum Tracking {
+    /// Poll each job using [`job::Show`].
+    Poll {
+        /// The time between each poll of a job.
+        interval: Duration,
+    },
+
+    /// Wait for the `job.finished` or `job.failed` webhook of each job.
+    Webhook {
+        /// The tracker, which webhook events are passed to. The webhook URL of each job is set to
+        /// [`WebhookTracker::url`], unless it's already set.
+        tracker: WebhookTracker,
+
+        /// If set, each job is also polled at this interval, in case a webhook is lost.
+        fallback_interval: Option<Duration>,
+    },
+}
+
+/// Receives webhook events for the jobs of a [`Batch`].
+///
+/// The tracker can be cloned, so a clone can be given to the web server which receives the
+/// webhooks. Each verified event (see [`Event::from_json`]) should be passed to
+/// [`WebhookTracker::handle`].
+#[derive(Clone, Debug)]
+pub struct WebhookTracker {
+    url: Arc<str>,
+    state: Arc<Mutex<TrackerState>>,
+}
+
+#[derive(Debug, Default)]
+struct TrackerState {
+    /// Jobs which are being waited for.
+    waiting: HashMap<String, oneshot::Sender<Job>>,
+    /// Jobs which finished before they were waited for.
+    completed: HashMap<String, Job>,
+}
+
+impl WebhookTracker {
+    /// Create a tracker for webhooks sent to `url`.
+    pub fn new(url: impl Into<String>) -> WebhookTracker {
+        WebhookTracker {
+            url: url.into().into(),
+            state: Default::default(),
+        }
+    }
+
+    /// The URL which webhooks should be sent to.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Handle a webhook event. Events other than `job.finished` and `job.failed` are ignored.
+    ///
+    /// The webhook for a job can arrive before the batch starts waiting for it, so events for
+    /// jobs which aren't being waited for are kept until they are. Only events for jobs created by
+    /// the batch should be passed to the tracker.
+    pub fn handle(&self, event: Event) {
+        if !matches!(event.event, EventKind::JobFinished | EventKind::JobFailed) {
+            return;
+        }
+        let mut state = self.state.lock().unwrap();
+        let job = event.job;
+        match state.waiting.remove(&job.id) {
+            Some(sender) => {
+                sender.send(job).ok();
+            }
+            None => {
+                state.completed.insert(job.id.clone(), job);
+            }
+        }
+    }
+
+    /// Start waiting for a job. Returns the job if its webhook has already been received.
+    fn wait(&self, id: &str) -> Result<Job, oneshot::Receiver<Job>> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(job) = state.completed.remove(id) {
+            return Ok(job);
+        }
+        let (sender, receiver) = oneshot::channel();
+        state.waiting.insert(id.to_string(), sender);
+        Err(receiver)
+    }
+
+    /// Stop waiting for a job.
+    fn forget(&self, id: &str) {
+        let mut state = self.state.lock().unwrap();
+        state.waiting.remove(id);
+        state.completed.remove(id);
+    }
+}
+
+impl Batch {
+    /// Run a job for each of `specs`, creating each job with `make_job`.
+    ///
+    /// Returns a stream of each spec with the result of its job, in the order the jobs complete.
+    /// The result is the job once it has either finished or failed, so its
+    /// [`status`](Job::status) should be checked, or an error if the job couldn't be created or
+    /// tracked.
+    ///
+    /// `make_job` may be called more than once for a spec, if creating the job is rate limited.
+    pub fn run<'c, B, T, S, F>(
+        &'c self,
+        client: &'c Client<B, T>,
+        specs: impl IntoIterator<Item = S> + 'c,
+        make_job: F,
+    ) -> impl Stream<Item = (S, Result<Job, Error>)> + 'c
+    where
+        B: From<Vec<u8>> + Send + Sync,
+        T: hapic::transport::Transport<B>,
+        S: 'c,
+        F: Fn(&S) -> job::Create<'static> + 'c,
+    {
+        let next_submission = Arc::new(tokio::sync::Mutex::new(Instant::now()));
+        let make_job = Arc::new(make_job);
+        futures_util::stream::iter(specs)
+            .map(move |spec| {
+                let next_submission = next_submission.clone();
+                let make_job = make_job.clone();
+                async move {
+                    let result = self
+                        .run_one(client, &next_submission, || make_job(&spec))
+                        .await;
+                    (spec, result)
+                }
+            })
+            .buffer_unordered(self.max_in_flight.max(1))
+    }
+
+    async fn run_one<B, T>(
+        &self,
+        client: &Client<B, T>,
+        next_submission: &tokio::sync::Mutex<Instant>,
+        make_job: impl Fn() -> job::Create<'static>,
+    ) -> Result<Job, Error>
+    where
+        B: From<Vec<u8>> + Send + Sync,
+        T: hapic::transport::Transport<B>,
+    {
+        let job = self
+            .retry(|| async {
+                let mut create = match self.rate_limit {
+                    Some(rate_limit) => {
+                        // The lock is held while waiting, so the next submission is timed from
+                        // when this one is actually made, rather than when it was scheduled.
+                        let mut next = next_submission.lock().await;
+                        tokio::time::sleep_until(*next).await;
+                        let create = make_job();
+                        *next = Instant::now() + rate_limit;
+                        create
+                    }
+                    None => make_job(),
+                };
+                if let Tracking::Webhook { tracker, .. } = &self.tracking {
+                    create
+                        .webhook_url
+                        .get_or_insert_with(|| tracker.url().to_string().into());
+                }
+                client.call(create).await
+            })
+            .await?;
+        if is_complete(&job) {
+            return Ok(job);
+        }
+
+        match &self.tracking {
+            Tracking::Poll { interval } => self.poll(client, &job.id, *interval).await,
+            Tracking::Webhook {
+                tracker,
+                fallback_interval,
+            } => {
+                let mut receiver = match tracker.wait(&job.id) {
+                    Ok(job) => return Ok(job),
+                    Err(receiver) => receiver,
+                };
+                let Some(interval) = *fallback_interval else {
+                    return receiver
+                        .await
+                        .map_err(|_| Error::Other("the webhook tracker was dropped".into()));
+                };
+                loop {
+                    match tokio::time::timeout(interval, &mut receiver).await {
+                        Ok(Ok(job)) => return Ok(job),
+                        // The webhook didn't arrive in time, or can't arrive, so poll the job.
+                        Ok(Err(_)) => {
+                            tracker.forget(&job.id);
+                            return self.poll(client, &job.id, interval).await;
+                        }
+                        Err(_) => {
+                            let job = self.show(client, &job.id).await?;
+                            if is_complete(&job) {
+                                tracker.forget(&job.id);
+                                return Ok(job);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Poll a job until it's complete.
+    async fn poll<B, T>(
+        &self,
+        client: &Client<B, T>,
+        id: &str,
+        interval: Duration,
+    ) -> Result<Job, Error>
+    where
+        B: From<Vec<u8>> + Send + Sync,
+        T: hapic::transport::Transport<B>,
+    {
+        loop {
+            tokio::time::sleep(interval).await;
+            let job = self.show(client, id).await?;
+            if is_complete(&job) {
+                return Ok(job);
+            }
+        }
+    }
+
+    async fn show<B, T>(&self, client: &Client<B, T>, id: &str) -> Result<Job, Error>
+    where
+        B: From<Vec<u8>> + Send + Sync,
+        T: hapic::transport::Transport<B>,
+    {
+        self.retry(|| client.call(job::Show { id: id.into() }))
+            .await
+    }
+
+    /// Make an API call, retrying it if it's rate limited.
+    async fn retry<O, Fut>(&self, call: impl Fn() -> Fut) -> Result<O, Error>
+    where
+        Fut: std::future::Future<Output = Result<O, Error>>,
+    {
+        let mut delay = self.retry_delay;
+        for _ in 0..self.max_retries {
+            match call().await {
+                Err(Error::HttpStatusNotSuccess { status, .. }) if status == 429 => {
+                    tokio::time::sleep(delay).await;
+                    delay = delay.saturating_mul(2);
+                }
+                result => return result,
+            }
+        }
+        call().await
+    }
+}
+
+/// Returns `true` if the job has finished or failed.
+fn is_complete(job: &Job) -> bool {
+    matches!(job.status, Some(Status::Finished | Status::Error))
+}
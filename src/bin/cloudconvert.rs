@@ -0,0 +1,404 @@
+//! The `cloudconvert` command line tool.
+//!
+//! This requires the `cli` feature. The API token is read from `--token` or the
+//! `CLOUDCONVERT_TOKEN` environment variable.
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+
+use clap::{Parser, Subcommand};
+
+use cloudconvert::conversion::ConvertFormats;
+use cloudconvert::task::{ImportUrl, Task};
+use cloudconvert::{
+    job, Error, Format, HyperClient, ImportConvertExport, Status, UploadConvertExport,
+};
+
+#[derive(Parser)]
+#[command(
+    name = "cloudconvert",
+    version,
+    about = "Convert files using CloudConvert"
+)]
+struct Cli {
+    /// The CloudConvert API token.
+    #[arg(long, env = "CLOUDCONVERT_TOKEN", hide_env_values = true)]
+    token: String,
+
+    /// The API endpoint, for example to use the sandbox API.
+    #[arg(
+        long,
+        env = "CLOUDCONVERT_ENDPOINT",
+        default_value = "https://api.cloudconvert.com/v2"
+    )]
+    endpoint: String,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Convert a local file or URL, and download the result.
+    Convert {
+        /// The file to convert, either a local path or an `http://` or `https://` URL.
+        input: String,
+
+        /// Where to save the converted file.
+        output: PathBuf,
+
+        /// The input format, if it can't be inferred from the input file name.
+        #[arg(long, value_parser = parse_format)]
+        input_format: Option<Format>,
+
+        /// The output format, if it can't be inferred from the output file name.
+        #[arg(long, value_parser = parse_format)]
+        output_format: Option<Format>,
+
+        /// The engine to use for the conversion.
+        #[arg(long)]
+        engine: Option<String>,
+
+        /// Extra options for the convert task, as `name=value`. Values are parsed as JSON if
+        /// possible, otherwise they're used as strings.
+        #[arg(long = "option", value_name = "NAME=VALUE", value_parser = parse_option)]
+        options: Vec<(String, serde_json::Value)>,
+
+        /// The tag to apply to the job.
+        #[arg(long)]
+        tag: Option<String>,
+
+        /// How often to check the status of the job, in seconds.
+        #[arg(long, default_value_t = 1.0, value_parser = parse_poll_interval)]
+        poll_interval: f64,
+    },
+
+    /// Show or list jobs.
+    #[command(subcommand)]
+    Job(JobCommand),
+
+    /// List the supported conversions.
+    Formats {
+        /// Only list conversions from this format.
+        #[arg(long, value_parser = parse_format)]
+        input: Option<Format>,
+
+        /// Only list conversions to this format.
+        #[arg(long, value_parser = parse_format)]
+        output: Option<Format>,
+
+        /// Only list conversions using this engine.
+        #[arg(long)]
+        engine: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum JobCommand {
+    /// Show a job and its tasks.
+    Show {
+        /// The ID of the job.
+        id: String,
+    },
+
+    /// List jobs.
+    List {
+        /// Only list jobs with this status: waiting, processing, finished or error.
+        #[arg(long, value_parser = parse_status)]
+        status: Option<Status>,
+
+        /// Only list jobs with this tag.
+        #[arg(long)]
+        tag: Option<String>,
+    },
+}
+
+fn parse_format(s: &str) -> Result<Format, String> {
+    if s.is_empty() {
+        return Err("the format can't be empty".to_string());
+    }
+    let format = Format::from(s.to_ascii_lowercase());
+    // CloudConvert supports formats which aren't listed in `Format`, so these are still used.
+    if let Format::Custom(custom) = &format {
+        eprintln!("warning: {custom} isn't a known format, so it's passed to CloudConvert as is");
+    }
+    Ok(format)
+}
+
+fn parse_poll_interval(s: &str) -> Result<f64, String> {
+    let seconds: f64 = s.parse().map_err(|_| format!("invalid number: {s}"))?;
+    // `Duration::from_secs_f64` panics on negative, non-finite or overflowing values.
+    if seconds > 0.0 && Duration::try_from_secs_f64(seconds).is_ok() {
+        Ok(seconds)
+    } else {
+        Err(format!("expected a positive number of seconds, got: {s}"))
+    }
+}
+
+fn parse_status(s: &str) -> Result<Status, String> {
+    serde_json::from_value(serde_json::Value::String(s.to_string()))
+        .map_err(|_| format!("unknown status: {s}"))
+}
+
+fn parse_option(s: &str) -> Result<(String, serde_json::Value), String> {
+    let (name, value) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected NAME=VALUE, got: {s}"))?;
+    let value = serde_json::from_str(value)
+        .unwrap_or_else(|_| serde_json::Value::String(value.to_string()));
+    Ok((name.to_string(), value))
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let mut client = HyperClient::default_client(&cli.token);
+    client.client.endpoint = cli.endpoint.into();
+
+    let result = match cli.command {
+        Command::Convert {
+            input,
+            output,
+            input_format,
+            output_format,
+            engine,
+            options,
+            tag,
+            poll_interval,
+        } => {
+            let options = ConvertArgs {
+                input_format,
+                output_format,
+                engine,
+                options,
+                tag,
+                poll_interval: Duration::from_secs_f64(poll_interval),
+            };
+            convert(&client, &input, &output, options).await
+        }
+        Command::Job(JobCommand::Show { id }) => show_job(&client, &id).await,
+        Command::Job(JobCommand::List { status, tag }) => list_jobs(&client, status, tag).await,
+        Command::Formats {
+            input,
+            output,
+            engine,
+        } => list_formats(&client, input, output, engine).await,
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+struct ConvertArgs {
+    input_format: Option<Format>,
+    output_format: Option<Format>,
+    engine: Option<String>,
+    options: Vec<(String, serde_json::Value)>,
+    tag: Option<String>,
+    poll_interval: Duration,
+}
+
+async fn convert(
+    client: &HyperClient,
+    input: &str,
+    output: &Path,
+    args: ConvertArgs,
+) -> Result<(), String> {
+    let is_url = input.starts_with("http://") || input.starts_with("https://");
+    let input_name = if is_url {
+        // Ignore any query string or fragment when inferring the format from a URL.
+        input.split(['?', '#']).next().unwrap_or(input)
+    } else {
+        input
+    };
+    let input_format = args.input_format.or_else(|| Format::from_path(input_name));
+    let output_format = args
+        .output_format
+        .or_else(|| Format::from_path(output))
+        .ok_or("couldn't infer the output format, use --output-format")?;
+
+    let mut create = if is_url {
+        ImportConvertExport {
+            tag: args.tag.map(Into::into),
+            import: ImportUrl {
+                url: input.into(),
+                filename: None,
+                headers: None,
+                extra: Default::default(),
+            },
+            input_format: input_format
+                .ok_or("couldn't infer the input format, use --input-format")?,
+            output_format,
+            export_inline: false,
+            timeout: None,
+            webhook_url: None,
+        }
+        .create_job()
+    } else {
+        UploadConvertExport {
+            tag: args.tag.map(Into::into),
+            input_format,
+            output_format,
+            timeout: None,
+            webhook_url: None,
+        }
+        .create_job()
+    };
+    if let Some(Task::Convert(convert)) = create.tasks.get_mut("convert") {
+        convert.engine = args.engine.map(Into::into);
+        convert.extra = args.options.into_iter().collect();
+    }
+    let job = client.call(create).await.map_err(api_error)?;
+    eprintln!("created job {}", job.id);
+
+    let result = run_job(client, &job, input, is_url, output, args.poll_interval).await;
+    // Like `Client::convert_bytes`, failing to delete the job isn't treated as an error, since
+    // CloudConvert deletes jobs automatically after 24 hours.
+    client
+        .call(job::Delete {
+            id: job.id.as_str().into(),
+        })
+        .await
+        .ok();
+    result
+}
+
+/// Upload the input file, if it's local, wait for the job, then save the converted file.
+async fn run_job(
+    client: &HyperClient,
+    job: &job::Job,
+    input: &str,
+    is_url: bool,
+    output: &Path,
+    poll_interval: Duration,
+) -> Result<(), String> {
+    if !is_url {
+        let contents =
+            std::fs::read(input).map_err(|err| format!("couldn't read {input}: {err}"))?;
+        let filename = Path::new(input)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("file");
+        let form = job
+            .get_task_by_name("import")
+            .and_then(|task| task.upload_form())
+            .ok_or("the upload task has no upload form")?;
+        client
+            .upload(&form, filename, &contents)
+            .await
+            .map_err(api_error)?;
+        eprintln!("uploaded {input}");
+    }
+
+    let job = client
+        .wait_for_job(&job.id, poll_interval)
+        .await
+        .map_err(api_error)?;
+    let url = job.export_url().map_err(api_error)?;
+    let contents = client.download(&url).await.map_err(api_error)?;
+    std::fs::write(output, contents)
+        .map_err(|err| format!("couldn't write {}: {err}", output.display()))?;
+    eprintln!("saved {}", output.display());
+    Ok(())
+}
+
+async fn show_job(client: &HyperClient, id: &str) -> Result<(), String> {
+    let job = client
+        .call(job::Show { id: id.into() })
+        .await
+        .map_err(api_error)?;
+    println!("job {}", job.id);
+    println!("  status: {}", status_str(job.status));
+    if let Some(tag) = &job.tag {
+        println!("  tag: {tag}");
+    }
+    for task in &job.tasks {
+        println!(
+            "  task {} ({}): {} {}",
+            task.name.as_deref().unwrap_or("-"),
+            task.id,
+            task.operation,
+            task.status.as_str(),
+        );
+        if let Some(message) = &task.status_message {
+            println!("    {message}");
+        }
+        if !task.operation.starts_with("export/") {
+            continue;
+        }
+        for file in task.exported_files() {
+            println!(
+                "    {} {}",
+                file.filename,
+                file.url.as_deref().unwrap_or("")
+            );
+        }
+    }
+    Ok(())
+}
+
+async fn list_jobs(
+    client: &HyperClient,
+    status: Option<Status>,
+    tag: Option<String>,
+) -> Result<(), String> {
+    let jobs = client
+        .list_all_jobs(status, tag.as_deref())
+        .await
+        .map_err(api_error)?;
+    for job in jobs {
+        println!(
+            "{}\t{}\t{}",
+            job.id,
+            status_str(job.status),
+            job.tag.as_deref().unwrap_or("-"),
+        );
+    }
+    Ok(())
+}
+
+async fn list_formats(
+    client: &HyperClient,
+    input: Option<Format>,
+    output: Option<Format>,
+    engine: Option<String>,
+) -> Result<(), String> {
+    let formats = client
+        .call(ConvertFormats {
+            input_format: input,
+            output_format: output,
+            engine: engine.map(Into::into),
+            include_options: false,
+        })
+        .await
+        .map_err(api_error)?;
+    for format in formats {
+        println!(
+            "{}\t{}\t{}",
+            format.input_format.str(),
+            format.output_format.str(),
+            format.engine,
+        );
+    }
+    Ok(())
+}
+
+fn status_str(status: Option<Status>) -> &'static str {
+    status.as_ref().map_or("-", Status::as_str)
+}
+
+fn api_error(err: Error) -> String {
+    match err {
+        Error::HttpStatusNotSuccess { status, body, .. } => {
+            format!("{status}: {}", String::from_utf8_lossy(&body))
+        }
+        Error::Other(message) => message.into_owned(),
+        err => format!("{err:?}"),
+    }
+}
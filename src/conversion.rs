@@ -0,0 +1,165 @@
+//! Types for querying which conversions are supported.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use super::{Client, Error, Format};
+
+/// List the supported conversions, optionally filtered by input format, output format or engine.
+///
+/// Docs: [api/v2/convert#convert-formats](https://cloudconvert.com/api/v2/convert#convert-formats)
+///
+/// To answer lots of queries without calling the API each time, see [`ConversionMatrix`].
+#[derive(Debug, Default)]
+pub struct ConvertFormats<'a> {
+    /// Only list conversions from this format.
+    pub input_format: Option<Format>,
+    /// Only list conversions to this format.
+    pub output_format: Option<Format>,
+    /// Only list conversions using this engine.
+    pub engine: Option<Cow<'a, str>>,
+    /// Include the available options of each conversion.
+    pub include_options: bool,
+}
+
+bodyless_api_call!(
+    <'a> GET ConvertFormats<'a> => ConvertFormatsOutput as Vec<ConversionFormat>,
+    |call, endpoint| {
+        let query = crate::query_string([
+            ("filter[input_format]", call.input_format.as_ref().map(Format::str)),
+            ("filter[output_format]", call.output_format.as_ref().map(Format::str)),
+            ("filter[engine]", call.engine.as_deref()),
+            ("include", call.include_options.then_some("options")),
+        ]);
+        format!("{endpoint}/convert/formats{query}")
+    }
+);
+
+#[doc(hidden)]
+#[derive(Deserialize)]
+pub struct ConvertFormatsOutput {
+    pub data: Vec<ConversionFormat>,
+}
+
+impl From<ConvertFormatsOutput> for Vec<ConversionFormat> {
+    fn from(output: ConvertFormatsOutput) -> Vec<ConversionFormat> {
+        output.data
+    }
+}
+
+/// A supported conversion, as returned by [`ConvertFormats`].
+#[derive(Clone, Debug, Deserialize)]
+pub struct ConversionFormat {
+    pub input_format: Format,
+    pub output_format: Format,
+
+    /// The engine which performs the conversion.
+    pub engine: String,
+
+    /// The number of credits the conversion costs.
+    #[serde(default)]
+    pub credits: Option<u32>,
+
+    /// The options supported by the conversion.
+    ///
+    /// This is only populated if [`ConvertFormats::include_options`] was set.
+    #[serde(default)]
+    pub options: Vec<ConversionOption>,
+}
+
+/// The schema of an option supported by a [`ConversionFormat`].
+#[derive(Clone, Debug, Deserialize)]
+pub struct ConversionOption {
+    /// The name of the option, as used in the [`crate::task::Convert`] task.
+    pub name: String,
+
+    /// The type of the option, for example `string`, `integer`, `boolean` or `enum`.
+    #[serde(rename = "type")]
+    pub kind: String,
+
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// The value used if the option isn't set.
+    #[serde(default)]
+    pub default: Option<serde_json::Value>,
+
+    /// If the option is an enum, the allowed values.
+    #[serde(default)]
+    pub possible_values: Option<Vec<serde_json::Value>>,
+}
+
+/// A lookup table of supported conversions.
+///
+/// This can be fetched once, using [`ConversionMatrix::fetch`], then queried without any further
+/// API calls. It can also be built from the output of a [`ConvertFormats`] call.
+#[derive(Clone, Debug, Default)]
+pub struct ConversionMatrix {
+    conversions: HashMap<Format, HashMap<Format, Vec<ConversionFormat>>>,
+}
+
+impl ConversionMatrix {
+    /// Fetch every supported conversion from the API.
+    pub async fn fetch<B, T>(client: &Client<B, T>) -> Result<ConversionMatrix, Error>
+    where
+        B: From<Vec<u8>> + Send + Sync,
+        T: hapic::transport::Transport<B>,
+    {
+        let formats = client.call(ConvertFormats::default()).await?;
+        Ok(formats.into_iter().collect())
+    }
+
+    /// Returns `true` if `input` can be converted to `output`.
+    pub fn can_convert(&self, input: &Format, output: &Format) -> bool {
+        !self.conversions(input, output).is_empty()
+    }
+
+    /// Returns the conversions from `input` to `output`, one for each supported engine.
+    pub fn conversions(&self, input: &Format, output: &Format) -> &[ConversionFormat] {
+        self.conversions
+            .get(input)
+            .and_then(|outputs| outputs.get(output))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the engines which can convert `input` to `output`.
+    pub fn engines<'a>(
+        &'a self,
+        input: &Format,
+        output: &Format,
+    ) -> impl Iterator<Item = &'a str> + 'a {
+        self.conversions(input, output)
+            .iter()
+            .map(|conversion| conversion.engine.as_str())
+    }
+
+    /// Returns the formats which `input` can be converted to.
+    pub fn outputs<'a>(&'a self, input: &Format) -> impl Iterator<Item = &'a Format> + 'a {
+        self.conversions
+            .get(input)
+            .into_iter()
+            .flat_map(HashMap::keys)
+    }
+
+    /// Add a conversion to the matrix.
+    pub fn insert(&mut self, conversion: ConversionFormat) {
+        self.conversions
+            .entry(conversion.input_format.clone())
+            .or_default()
+            .entry(conversion.output_format.clone())
+            .or_default()
+            .push(conversion);
+    }
+}
+
+impl FromIterator<ConversionFormat> for ConversionMatrix {
+    fn from_iter<I: IntoIterator<Item = ConversionFormat>>(iter: I) -> ConversionMatrix {
+        let mut matrix = ConversionMatrix::default();
+        for conversion in iter {
+            matrix.insert(conversion);
+        }
+        matrix
+    }
+}
@@ -1,11 +1,12 @@
 use std::borrow::Cow;
+use std::path::Path;
 
 macro_rules! format_enum {
     (
         $(#[$meta:meta])*
         $vis:vis enum $Format:ident {
             $Custom:ident($CustomType:ty),
-            $($Variant:ident = $str:literal,)*
+            $($Variant:ident = $str:literal => $Category:ident, $mime:literal,)*
         }
     ) => {
         $(#[$meta])*
@@ -16,12 +17,54 @@ macro_rules! format_enum {
         }
 
         impl $Format {
+            /// Every known format, that is, every variant except custom formats.
+            pub const ALL: &[$Format] = &[$($Format::$Variant,)*];
+
             pub fn str(&self) -> &str {
                 match self {
                     $($Format::$Variant => $str,)*
                     $Format::$Custom(custom) => custom.as_ref(),
                 }
             }
+
+            /// Returns the category of the format, or `None` for custom formats.
+            pub fn category(&self) -> Option<FormatCategory> {
+                match self {
+                    $($Format::$Variant => Some(FormatCategory::$Category),)*
+                    $Format::$Custom(_) => None,
+                }
+            }
+
+            /// Returns the MIME type of the format, or `None` for custom formats.
+            pub fn mime_type(&self) -> Option<&'static str> {
+                match self {
+                    $($Format::$Variant => Some($mime),)*
+                    $Format::$Custom(_) => None,
+                }
+            }
+
+            /// Returns the format with the given MIME type, if there is one.
+            ///
+            /// Any parameters (such as `; charset=utf-8`) are ignored, so this can be used directly
+            /// with the value of a `Content-Type` header. Where multiple formats share a MIME type
+            /// (for example `jpg` and `jpeg`), the first listed format is returned.
+            pub fn from_mime(mime: &str) -> Option<$Format> {
+                let mime = mime.split(';').next().unwrap_or_default().trim();
+                $(
+                    if mime.eq_ignore_ascii_case($mime) {
+                        return Some($Format::$Variant);
+                    }
+                )*
+                None
+            }
+
+            /// Returns the known (non-custom) format with the given string value, if there is one.
+            fn known(s: &str) -> Option<$Format> {
+                match s {
+                    $($str => Some($Format::$Variant),)*
+                    _ => None,
+                }
+            }
         }
 
         impl From<Cow<'static, str>> for $Format {
@@ -47,215 +90,299 @@ macro_rules! format_enum {
     };
 }
 
+/// The category of a [`Format`], as grouped by CloudConvert.
+///
+/// Some formats (such as `pdf` or `eps`) could belong to more than one category, but
+/// [`Format::category`] only returns the one the format is listed under.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FormatCategory {
+    Archive,
+    Audio,
+    Video,
+    Cad,
+    Document,
+    Ebook,
+    Font,
+    Image,
+    Presentation,
+    Spreadsheet,
+    Vector,
+}
+
 format_enum!(
     /// A format supported by CloudConvert.
     ///
     /// Serializes to the string value used in the CloudConvert API.
     pub enum Format {
         Custom(Cow<'static, str>),
-        SevenZ = "7z",
-        Ace = "ace",
-        Alz = "alz",
-        Arc = "arc",
-        Arj = "arj",
-        Bz = "bz",
-        Bz2 = "bz2",
-        Cab = "cab",
-        Cpio = "cpio",
-        Deb = "deb",
-        Dmg = "dmg",
-        Gz = "gz",
-        Img = "img",
-        Iso = "iso",
-        Jar = "jar",
-        Lha = "lha",
-        Lz = "lz",
-        Lzma = "lzma",
-        Lzo = "lzo",
-        Rar = "rar",
-        Rpm = "rpm",
-        Rz = "rz",
-        Tar = "tar",
-        Tar7z = "tar.7z",
-        TarBz = "tar.bz",
-        TarBz2 = "tar.bz2",
-        TarGz = "tar.gz",
-        TarLzo = "tar.lzo",
-        TarXz = "tar.xz",
-        TarZ = "tar.z",
-        Tbz = "tbz",
-        Tbz2 = "tbz2",
-        Tgz = "tgz",
-        Tz = "tz",
-        Tzo = "tzo",
-        Xz = "xz",
-        Z = "z",
-        Zip = "zip",
-        //3g2 = "3g2",
-        //3gp = "3gp",
-        //3gpp = "3gpp",
-        Aac = "aac",
-        Ac3 = "ac3",
-        Aif = "aif",
-        Aifc = "aifc",
-        Aiff = "aiff",
-        Amr = "amr",
-        Au = "au",
-        Avi = "avi",
-        Caf = "caf",
-        Cavs = "cavs",
-        Dv = "dv",
-        Dvr = "dvr",
-        Flac = "flac",
-        Flv = "flv",
-        Gif = "gif",
-        M2ts = "m2ts",
-        M4a = "m4a",
-        M4b = "m4b",
-        M4v = "m4v",
-        Mkv = "mkv",
-        Mod = "mod",
-        Mov = "mov",
-        Mp3 = "mp3",
-        Mp4 = "mp4",
-        Mpeg = "mpeg",
-        Mpg = "mpg",
-        Mts = "mts",
-        Mxf = "mxf",
-        Oga = "oga",
-        Ogg = "ogg",
-        Rm = "rm",
-        Rmvb = "rmvb",
-        Swf = "swf",
-        Ts = "ts",
-        Vob = "vob",
-        Voc = "voc",
-        Wav = "wav",
-        Weba = "weba",
-        Webm = "webm",
-        Wma = "wma",
-        Wmv = "wmv",
-        Wtv = "wtv",
-        Ai = "ai",
-        Cdr = "cdr",
-        Cgm = "cgm",
-        Dwg = "dwg",
-        Dxf = "dxf",
-        Emf = "emf",
-        Eps = "eps",
-        Pdf = "pdf",
-        Ps = "ps",
-        Sk = "sk",
-        Sk1 = "sk1",
-        Svg = "svg",
-        Svgz = "svgz",
-        Vsd = "vsd",
-        Wmf = "wmf",
-        //3fr = "3fr",
-        Abw = "abw",
-        Arw = "arw",
-        Avif = "avif",
-        Azw = "azw",
-        Azw3 = "azw3",
-        Azw4 = "azw4",
-        Bmp = "bmp",
-        Cbc = "cbc",
-        Cbr = "cbr",
-        Cbz = "cbz",
-        Chm = "chm",
-        Cr2 = "cr2",
-        Cr3 = "cr3",
-        Crw = "crw",
-        Csv = "csv",
-        Dcr = "dcr",
-        Djvu = "djvu",
-        Dng = "dng",
-        Doc = "doc",
-        Docm = "docm",
-        Docx = "docx",
-        Dot = "dot",
-        Dotx = "dotx",
-        Dps = "dps",
-        Epub = "epub",
-        Erf = "erf",
-        Et = "et",
-        Fb2 = "fb2",
-        Heic = "heic",
-        Heif = "heif",
-        Htm = "htm",
-        Html = "html",
-        Htmlz = "htmlz",
-        Hwp = "hwp",
-        Ico = "ico",
-        Jfif = "jfif",
-        Jpeg = "jpeg",
-        Jpg = "jpg",
-        Key = "key",
-        Lit = "lit",
-        Lrf = "lrf",
-        Lwp = "lwp",
-        Md = "md",
-        Mobi = "mobi",
-        Mos = "mos",
-        Mrw = "mrw",
-        Nef = "nef",
-        Numbers = "numbers",
-        Odd = "odd",
-        Odg = "odg",
-        Odp = "odp",
-        Ods = "ods",
-        Odt = "odt",
-        Orf = "orf",
-        Pages = "pages",
-        Pdb = "pdb",
-        Pef = "pef",
-        Pml = "pml",
-        Png = "png",
-        Pot = "pot",
-        Potx = "potx",
-        Ppm = "ppm",
-        Pps = "pps",
-        Ppsx = "ppsx",
-        Ppt = "ppt",
-        Pptm = "pptm",
-        Pptx = "pptx",
-        Prc = "prc",
-        Psd = "psd",
-        Raf = "raf",
-        Raw = "raw",
-        Rb = "rb",
-        Rst = "rst",
-        Rtf = "rtf",
-        Rw2 = "rw2",
-        Snb = "snb",
-        Tcr = "tcr",
-        Tex = "tex",
-        Tif = "tif",
-        Tiff = "tiff",
-        Txt = "txt",
-        Txtz = "txtz",
-        Webp = "webp",
-        Wpd = "wpd",
-        Wps = "wps",
-        X3f = "x3f",
-        Xcf = "xcf",
-        Xls = "xls",
-        Xlsm = "xlsm",
-        Xlsx = "xlsx",
-        Xps = "xps",
-        Zabw = "zabw",
-        Eot = "eot",
-        Otf = "otf",
-        Ttf = "ttf",
-        Woff = "woff",
-        Woff2 = "woff2",
-        Icns = "icns",
+        // Formats are listed alphabetically within each category, except that where formats share
+        // a MIME type, the canonical one is listed first. The order matters, since `from_mime`
+        // returns the first listed format.
+        // Archive
+        SevenZ = "7z" => Archive, "application/x-7z-compressed",
+        Ace = "ace" => Archive, "application/x-ace-compressed",
+        Alz = "alz" => Archive, "application/x-alz-compressed",
+        Arc = "arc" => Archive, "application/x-freearc",
+        Arj = "arj" => Archive, "application/x-arj",
+        Bz = "bz" => Archive, "application/x-bzip",
+        Bz2 = "bz2" => Archive, "application/x-bzip2",
+        Cab = "cab" => Archive, "application/vnd.ms-cab-compressed",
+        Cpio = "cpio" => Archive, "application/x-cpio",
+        Deb = "deb" => Archive, "application/vnd.debian.binary-package",
+        Dmg = "dmg" => Archive, "application/x-apple-diskimage",
+        Gz = "gz" => Archive, "application/gzip",
+        Img = "img" => Archive, "application/x-raw-disk-image",
+        Iso = "iso" => Archive, "application/x-iso9660-image",
+        Jar = "jar" => Archive, "application/java-archive",
+        Lha = "lha" => Archive, "application/x-lzh-compressed",
+        Lz = "lz" => Archive, "application/x-lzip",
+        Lzma = "lzma" => Archive, "application/x-lzma",
+        Lzo = "lzo" => Archive, "application/x-lzop",
+        Rar = "rar" => Archive, "application/vnd.rar",
+        Rpm = "rpm" => Archive, "application/x-rpm",
+        Rz = "rz" => Archive, "application/x-rzip",
+        Tar = "tar" => Archive, "application/x-tar",
+        Tar7z = "tar.7z" => Archive, "application/x-7z-compressed-tar",
+        TarBz = "tar.bz" => Archive, "application/x-bzip-compressed-tar",
+        TarBz2 = "tar.bz2" => Archive, "application/x-bzip2-compressed-tar",
+        TarGz = "tar.gz" => Archive, "application/x-compressed-tar",
+        TarLzo = "tar.lzo" => Archive, "application/x-tzo",
+        TarXz = "tar.xz" => Archive, "application/x-xz-compressed-tar",
+        TarZ = "tar.z" => Archive, "application/x-tarz",
+        Tbz = "tbz" => Archive, "application/x-bzip-compressed-tar",
+        Tbz2 = "tbz2" => Archive, "application/x-bzip2-compressed-tar",
+        Tgz = "tgz" => Archive, "application/x-compressed-tar",
+        Tz = "tz" => Archive, "application/x-tarz",
+        Tzo = "tzo" => Archive, "application/x-tzo",
+        Xz = "xz" => Archive, "application/x-xz",
+        Z = "z" => Archive, "application/x-compress",
+        Zip = "zip" => Archive, "application/zip",
+        // Audio
+        Aac = "aac" => Audio, "audio/aac",
+        Ac3 = "ac3" => Audio, "audio/ac3",
+        Aif = "aif" => Audio, "audio/x-aiff",
+        Aifc = "aifc" => Audio, "audio/x-aifc",
+        Aiff = "aiff" => Audio, "audio/aiff",
+        Amr = "amr" => Audio, "audio/amr",
+        Au = "au" => Audio, "audio/basic",
+        Caf = "caf" => Audio, "audio/x-caf",
+        Dss = "dss" => Audio, "audio/x-dss",
+        Flac = "flac" => Audio, "audio/flac",
+        M4a = "m4a" => Audio, "audio/mp4",
+        M4b = "m4b" => Audio, "audio/x-m4b",
+        Mp3 = "mp3" => Audio, "audio/mpeg",
+        Oga = "oga" => Audio, "audio/ogg",
+        Opus = "opus" => Audio, "audio/opus",
+        Voc = "voc" => Audio, "audio/x-voc",
+        Wav = "wav" => Audio, "audio/wav",
+        Weba = "weba" => Audio, "audio/webm",
+        Wma = "wma" => Audio, "audio/x-ms-wma",
+        // Video
+        ThreeG2 = "3g2" => Video, "video/3gpp2",
+        ThreeGp = "3gp" => Video, "video/3gpp",
+        ThreeGpp = "3gpp" => Video, "video/3gpp",
+        Avi = "avi" => Video, "video/x-msvideo",
+        Cavs = "cavs" => Video, "video/x-cavs",
+        Dv = "dv" => Video, "video/x-dv",
+        Dvr = "dvr" => Video, "video/x-ms-dvr",
+        Flv = "flv" => Video, "video/x-flv",
+        Ts = "ts" => Video, "video/mp2t",
+        M2ts = "m2ts" => Video, "video/mp2t",
+        M4v = "m4v" => Video, "video/x-m4v",
+        Mkv = "mkv" => Video, "video/x-matroska",
+        Mod = "mod" => Video, "video/x-mod",
+        Mov = "mov" => Video, "video/quicktime",
+        Mp4 = "mp4" => Video, "video/mp4",
+        Mpeg = "mpeg" => Video, "video/mpeg",
+        Mpg = "mpg" => Video, "video/mpeg",
+        Mts = "mts" => Video, "video/vnd.dlna.mpeg-tts",
+        Mxf = "mxf" => Video, "application/mxf",
+        Ogg = "ogg" => Video, "video/ogg",
+        Rm = "rm" => Video, "application/vnd.rn-realmedia",
+        Rmvb = "rmvb" => Video, "application/vnd.rn-realmedia-vbr",
+        Swf = "swf" => Video, "application/x-shockwave-flash",
+        Vob = "vob" => Video, "video/x-ms-vob",
+        Webm = "webm" => Video, "video/webm",
+        Wmv = "wmv" => Video, "video/x-ms-wmv",
+        Wtv = "wtv" => Video, "video/x-ms-wtv",
+        // Cad
+        Dwg = "dwg" => Cad, "image/vnd.dwg",
+        Dxf = "dxf" => Cad, "image/vnd.dxf",
+        // Vector
+        Ai = "ai" => Vector, "application/illustrator",
+        Cdr = "cdr" => Vector, "application/vnd.corel-draw",
+        Cgm = "cgm" => Vector, "image/cgm",
+        Emf = "emf" => Vector, "image/emf",
+        Ps = "ps" => Vector, "application/postscript",
+        Eps = "eps" => Vector, "application/postscript",
+        Sk = "sk" => Vector, "image/x-skencil",
+        Sk1 = "sk1" => Vector, "image/x-sk1",
+        Svg = "svg" => Vector, "image/svg+xml",
+        Svgz = "svgz" => Vector, "image/svg+xml-compressed",
+        Vsd = "vsd" => Vector, "application/vnd.visio",
+        Wmf = "wmf" => Vector, "image/wmf",
+        // Document
+        Abw = "abw" => Document, "application/x-abiword",
+        Djvu = "djvu" => Document, "image/vnd.djvu",
+        Doc = "doc" => Document, "application/msword",
+        Docm = "docm" => Document, "application/vnd.ms-word.document.macroenabled.12",
+        Docx = "docx" => Document, "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        Dot = "dot" => Document, "application/msword-template",
+        Dotx = "dotx" => Document, "application/vnd.openxmlformats-officedocument.wordprocessingml.template",
+        Html = "html" => Document, "text/html",
+        Htm = "htm" => Document, "text/html",
+        Hwp = "hwp" => Document, "application/x-hwp",
+        Lwp = "lwp" => Document, "application/vnd.lotus-wordpro",
+        Md = "md" => Document, "text/markdown",
+        Mhtml = "mhtml" => Document, "multipart/related",
+        Mht = "mht" => Document, "multipart/related",
+        Odf = "odf" => Document, "application/vnd.oasis.opendocument.formula",
+        Odt = "odt" => Document, "application/vnd.oasis.opendocument.text",
+        Ott = "ott" => Document, "application/vnd.oasis.opendocument.text-template",
+        Pages = "pages" => Document, "application/vnd.apple.pages",
+        Pdf = "pdf" => Document, "application/pdf",
+        Rst = "rst" => Document, "text/x-rst",
+        Rtf = "rtf" => Document, "application/rtf",
+        Tex = "tex" => Document, "application/x-tex",
+        Txt = "txt" => Document, "text/plain",
+        Wpd = "wpd" => Document, "application/vnd.wordperfect",
+        Wps = "wps" => Document, "application/vnd.ms-works",
+        Xps = "xps" => Document, "application/vnd.ms-xpsdocument",
+        Zabw = "zabw" => Document, "application/x-abiword-compressed",
+        // Ebook
+        Azw = "azw" => Ebook, "application/vnd.amazon.ebook",
+        Azw3 = "azw3" => Ebook, "application/vnd.amazon.mobi8-ebook",
+        Azw4 = "azw4" => Ebook, "application/x-azw4",
+        Cbc = "cbc" => Ebook, "application/x-cbc",
+        Cbr = "cbr" => Ebook, "application/vnd.comicbook-rar",
+        Cbz = "cbz" => Ebook, "application/vnd.comicbook+zip",
+        Chm = "chm" => Ebook, "application/vnd.ms-htmlhelp",
+        Epub = "epub" => Ebook, "application/epub+zip",
+        Fb2 = "fb2" => Ebook, "application/x-fictionbook+xml",
+        Htmlz = "htmlz" => Ebook, "application/x-htmlz",
+        Lit = "lit" => Ebook, "application/x-ms-reader",
+        Lrf = "lrf" => Ebook, "application/x-sony-bbeb",
+        Mobi = "mobi" => Ebook, "application/x-mobipocket-ebook",
+        Pdb = "pdb" => Ebook, "application/vnd.palm",
+        Pml = "pml" => Ebook, "application/x-pml",
+        Prc = "prc" => Ebook, "application/x-mobipocket-ebook-prc",
+        Rb = "rb" => Ebook, "application/x-rocketbook",
+        Snb = "snb" => Ebook, "application/x-snb",
+        Tcr = "tcr" => Ebook, "application/x-tcr",
+        Txtz = "txtz" => Ebook, "application/x-txtz",
+        // Font
+        Eot = "eot" => Font, "application/vnd.ms-fontobject",
+        Otf = "otf" => Font, "font/otf",
+        Ttf = "ttf" => Font, "font/ttf",
+        Woff = "woff" => Font, "font/woff",
+        Woff2 = "woff2" => Font, "font/woff2",
+        // Image
+        ThreeFr = "3fr" => Image, "image/x-hasselblad-3fr",
+        Arw = "arw" => Image, "image/x-sony-arw",
+        Avif = "avif" => Image, "image/avif",
+        Bmp = "bmp" => Image, "image/bmp",
+        Cr2 = "cr2" => Image, "image/x-canon-cr2",
+        Cr3 = "cr3" => Image, "image/x-canon-cr3",
+        Crw = "crw" => Image, "image/x-canon-crw",
+        Dcr = "dcr" => Image, "image/x-kodak-dcr",
+        Dng = "dng" => Image, "image/x-adobe-dng",
+        Erf = "erf" => Image, "image/x-epson-erf",
+        Gif = "gif" => Image, "image/gif",
+        Heic = "heic" => Image, "image/heic",
+        Heif = "heif" => Image, "image/heif",
+        Heics = "heics" => Image, "image/heic-sequence",
+        Heifs = "heifs" => Image, "image/heif-sequence",
+        Hif = "hif" => Image, "image/x-fuji-hif",
+        Icns = "icns" => Image, "image/icns",
+        Ico = "ico" => Image, "image/vnd.microsoft.icon",
+        Jpg = "jpg" => Image, "image/jpeg",
+        Jpeg = "jpeg" => Image, "image/jpeg",
+        Jfif = "jfif" => Image, "image/pjpeg",
+        Jp2 = "jp2" => Image, "image/jp2",
+        Jxl = "jxl" => Image, "image/jxl",
+        Mos = "mos" => Image, "image/x-leaf-mos",
+        Mrw = "mrw" => Image, "image/x-minolta-mrw",
+        Nef = "nef" => Image, "image/x-nikon-nef",
+        Nrw = "nrw" => Image, "image/x-nikon-nrw",
+        Odd = "odd" => Image, "application/vnd.oasis.opendocument.graphics-template",
+        Odg = "odg" => Image, "application/vnd.oasis.opendocument.graphics",
+        Orf = "orf" => Image, "image/x-olympus-orf",
+        Pef = "pef" => Image, "image/x-pentax-pef",
+        Png = "png" => Image, "image/png",
+        Ppm = "ppm" => Image, "image/x-portable-pixmap",
+        Psd = "psd" => Image, "image/vnd.adobe.photoshop",
+        Raf = "raf" => Image, "image/x-fuji-raf",
+        Raw = "raw" => Image, "image/x-panasonic-raw",
+        Rw2 = "rw2" => Image, "image/x-panasonic-rw2",
+        Srw = "srw" => Image, "image/x-samsung-srw",
+        Tga = "tga" => Image, "image/x-tga",
+        Tiff = "tiff" => Image, "image/tiff",
+        Tif = "tif" => Image, "image/tiff",
+        Webp = "webp" => Image, "image/webp",
+        X3f = "x3f" => Image, "image/x-sigma-x3f",
+        Xcf = "xcf" => Image, "image/x-xcf",
+        // Presentation
+        Dps = "dps" => Presentation, "application/vnd.kingsoft.presentation",
+        Key = "key" => Presentation, "application/vnd.apple.keynote",
+        Odp = "odp" => Presentation, "application/vnd.oasis.opendocument.presentation",
+        Pot = "pot" => Presentation, "application/vnd.ms-powerpoint.template",
+        Potx = "potx" => Presentation, "application/vnd.openxmlformats-officedocument.presentationml.template",
+        Pps = "pps" => Presentation, "application/vnd.ms-powerpoint.slideshow",
+        Ppsx = "ppsx" => Presentation, "application/vnd.openxmlformats-officedocument.presentationml.slideshow",
+        Ppt = "ppt" => Presentation, "application/vnd.ms-powerpoint",
+        Pptm = "pptm" => Presentation, "application/vnd.ms-powerpoint.presentation.macroenabled.12",
+        Pptx = "pptx" => Presentation, "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+        // Spreadsheet
+        Csv = "csv" => Spreadsheet, "text/csv",
+        Et = "et" => Spreadsheet, "application/vnd.kingsoft.spreadsheet",
+        Numbers = "numbers" => Spreadsheet, "application/vnd.apple.numbers",
+        Ods = "ods" => Spreadsheet, "application/vnd.oasis.opendocument.spreadsheet",
+        Xls = "xls" => Spreadsheet, "application/vnd.ms-excel",
+        Xlsb = "xlsb" => Spreadsheet, "application/vnd.ms-excel.sheet.binary.macroenabled.12",
+        Xlsm = "xlsm" => Spreadsheet, "application/vnd.ms-excel.sheet.macroenabled.12",
+        Xlsx = "xlsx" => Spreadsheet, "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
     }
 );
 
+impl Format {
+    /// Infer the format of a file from its path.
+    ///
+    /// The file extension is matched case-insensitively against the known formats, preferring the
+    /// longest match, so `archive.tar.gz` is [`Format::TarGz`] rather than [`Format::Gz`]. Returns
+    /// `None` if the file has no extension, or the extension isn't a known format.
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Format> {
+        let name = path.as_ref().file_name()?.to_str()?.to_ascii_lowercase();
+        name.match_indices('.')
+            .filter(|(idx, _)| *idx != 0)
+            .find_map(|(idx, _)| Format::known(&name[idx + 1..]))
+    }
+}
+
 // Serialize to the string value
 impl serde::Serialize for Format {
     fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         serializer.serialize_str(self.str())
     }
 }
+
+// Deserialize from the string value
+impl<'de> serde::Deserialize<'de> for Format {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Format, D::Error> {
+        String::deserialize(deserializer).map(Format::from)
+    }
+}
+
+// Formats are compared by their string value, so `Format::Custom("pdf")` equals `Format::Pdf`.
+impl PartialEq for Format {
+    fn eq(&self, other: &Format) -> bool {
+        self.str() == other.str()
+    }
+}
+
+impl Eq for Format {}
+
+impl std::hash::Hash for Format {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.str().hash(state)
+    }
+}
@@ -1,7 +1,8 @@
 //! Types for creating and viewing jobs.
 
-use std::borrow::Cow;
+use std::borrow::{Borrow, Cow};
 use std::collections::HashMap;
+use std::hash::Hash;
 
 use serde::{Deserialize, Serialize};
 
@@ -13,13 +14,129 @@ use super::{task, Status};
 ///
 /// See the implementation of [`crate::ImportConvertExport::create_job`] for an example of creating
 /// a job.
+///
+/// This serializes to the same JSON as the body of the API call, and can be deserialized from it,
+/// so jobs can be stored and created later. Any [`task::Secret`]s are serialized as their real
+/// values, so stored jobs should be protected like any other credentials.
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Create<'a> {
     /// The tasks within this job. A map of names to tasks.
     pub tasks: HashMap<String, task::Task<'a>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub tag: Option<Cow<'a, str>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub webhook_url: Option<Cow<'a, str>>,
 }
 
+impl Create<'static> {
+    /// Create a job from a JSON or YAML template, such as the JSON produced by the CloudConvert
+    /// job builder.
+    ///
+    /// The template has the same structure as the body of a [`Create`] call: a `tasks` object
+    /// mapping names to tasks (each with an `operation` field), and optionally a `tag` and
+    /// `webhook_url`. Within any string in the template, `{{name}}` is replaced with the value of
+    /// the variable `name`, for example:
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use cloudconvert::job::Create;
+    ///
+    /// let template = r#"
+    /// tasks:
+    ///   import:
+    ///     operation: import/url
+    ///     url: "{{url}}"
+    ///   convert:
+    ///     operation: convert
+    ///     input: import
+    ///     output_format: pdf
+    ///     filename: "{{ name }}.pdf"
+    ///   export:
+    ///     operation: export/url
+    ///     input: convert
+    /// "#;
+    /// let job = Create::from_template(
+    ///     template,
+    ///     &HashMap::from([("url", "https://example.com/cv.docx"), ("name", "cv")]),
+    /// )
+    /// .unwrap();
+    /// assert_eq!(job.tasks.len(), 3);
+    /// ```
+    pub fn from_template<K, V>(
+        template: &str,
+        variables: &HashMap<K, V>,
+    ) -> Result<Create<'static>, TemplateError>
+    where
+        K: Borrow<str> + Hash + Eq,
+        V: AsRef<str>,
+    {
+        // YAML is a superset of JSON, so this parses both.
+        let mut template: serde_json::Value =
+            serde_yaml::from_str(template).map_err(TemplateError::Parse)?;
+        substitute_variables(&mut template, variables)?;
+        serde_json::from_value(template).map_err(TemplateError::InvalidJob)
+    }
+}
+
+/// An error returned by [`Create::from_template`].
+#[derive(Debug)]
+pub enum TemplateError {
+    /// The template isn't valid JSON or YAML.
+    Parse(serde_yaml::Error),
+
+    /// The template uses a variable which wasn't given.
+    UndefinedVariable(String),
+
+    /// The template contains a `{{` without a matching `}}`.
+    UnclosedVariable,
+
+    /// The template isn't a valid job, for example because a task has an unknown operation, or is
+    /// missing a required field.
+    InvalidJob(serde_json::Error),
+}
+
+/// Replace `{{name}}` with the value of the variable `name`, in every string within `value`.
+fn substitute_variables<K, V>(
+    value: &mut serde_json::Value,
+    variables: &HashMap<K, V>,
+) -> Result<(), TemplateError>
+where
+    K: Borrow<str> + Hash + Eq,
+    V: AsRef<str>,
+{
+    match value {
+        serde_json::Value::String(s) if s.contains("{{") => {
+            let mut out = String::with_capacity(s.len());
+            let mut rest = s.as_str();
+            while let Some(start) = rest.find("{{") {
+                out.push_str(&rest[..start]);
+                let after = &rest[start + 2..];
+                let end = after.find("}}").ok_or(TemplateError::UnclosedVariable)?;
+                let name = after[..end].trim();
+                let value = variables
+                    .get(name)
+                    .ok_or_else(|| TemplateError::UndefinedVariable(name.to_string()))?;
+                out.push_str(value.as_ref());
+                rest = &after[end + 2..];
+            }
+            out.push_str(rest);
+            *s = out;
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                substitute_variables(item, variables)?;
+            }
+        }
+        serde_json::Value::Object(fields) => {
+            for field in fields.values_mut() {
+                substitute_variables(field, variables)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
 /// The actual request body that is sent to the API server for a [`Create`] call.
 #[doc(hidden)]
 #[derive(Serialize)]
@@ -48,21 +165,243 @@ impl<'a> From<Create<'a>> for CreateJobRequest<'a> {
 #[doc(hidden)]
 #[derive(Deserialize)]
 pub struct JobsOutput {
+    #[serde(deserialize_with = "deserialize_job")]
     pub data: Job,
 }
 
 impl From<JobsOutput> for Job {
     fn from(output: JobsOutput) -> Job {
-        let mut job = output.data;
-        // Ensure the task `job_id` field is filled out.
-        for task in job.tasks.iter_mut() {
-            if let Some(task_job_id) = task.job_id.as_deref() {
-                assert_eq!(task_job_id, job.id);
-            } else {
-                task.job_id = Some(job.id.to_string());
+        output.data
+    }
+}
+
+/// Fill out the `job_id` of each task of a job, returning an error if a task belongs to a
+/// different job.
+pub(crate) fn fill_task_job_ids(mut job: Job) -> Result<Job, String> {
+    for task in job.tasks.iter_mut() {
+        match task.job_id.as_deref() {
+            Some(job_id) if job_id != job.id => {
+                return Err(format!(
+                    "task {} of job {} has job_id {job_id}",
+                    task.id, job.id
+                ));
             }
+            Some(_) => {}
+            None => task.job_id = Some(job.id.clone()),
+        }
+    }
+    Ok(job)
+}
+
+fn deserialize_job<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Job, D::Error> {
+    fill_task_job_ids(Job::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+}
+
+fn deserialize_jobs<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<Job>, D::Error> {
+    Vec::<Job>::deserialize(deserializer)?
+        .into_iter()
+        .map(fill_task_job_ids)
+        .collect::<Result<_, _>>()
+        .map_err(serde::de::Error::custom)
+}
+
+/// Create a job using the synchronous API, which doesn't respond until the job has finished or
+/// failed, so a short conversion completes in one request.
+///
+/// The job can't contain [`task::ImportUpload`] tasks, since the file couldn't be uploaded until
+/// the job has completed. Jobs which take too long are returned before they complete, so the
+/// [`status`](Job::status) of the returned job should be checked, and [`Wait`] or
+/// [`crate::Client::wait_for_job`] used if it's still processing. The files exported by a finished
+/// job are returned by [`Job::exported_files`].
+///
+/// Docs: [cloudconvert.com/api/v2/jobs#jobs-create](https://cloudconvert.com/api/v2/jobs#jobs-create)
+#[derive(Debug)]
+pub struct CreateSync<'a> {
+    /// The job to create.
+    pub job: Create<'a>,
+
+    /// The synchronous API endpoint. If `None`, this is derived from the client's endpoint using
+    /// [`sync_endpoint`].
+    pub endpoint: Option<Cow<'a, str>>,
+}
+
+impl<'a> From<Create<'a>> for CreateSync<'a> {
+    fn from(job: Create<'a>) -> CreateSync<'a> {
+        CreateSync {
+            job,
+            endpoint: None,
         }
-        job
+    }
+}
+
+impl<'a> crate::ApiCall for CreateSync<'a> {}
+
+impl<'a> hapic::JsonApiCall for CreateSync<'a> {
+    type Output = Job;
+    type JsonResponse = JobsOutput;
+    type JsonRequest = CreateJobRequest<'a>;
+
+    fn uri(&self, endpoint: &str) -> String {
+        match &self.endpoint {
+            Some(endpoint) => format!("{endpoint}/jobs"),
+            None => format!("{}/jobs", sync_endpoint(endpoint)),
+        }
+    }
+
+    fn try_into_request(self) -> Result<CreateJobRequest<'a>, hapic::Error> {
+        Ok(self.job.into())
+    }
+
+    fn parse_json_response(
+        status: hapic::http::StatusCode,
+        content_type: Option<hapic::http::HeaderValue>,
+        raw_resp: Vec<u8>,
+        resp: serde_json::Result<JobsOutput>,
+    ) -> Result<Job, hapic::Error> {
+        if status.is_success() {
+            Ok(resp?.into())
+        } else {
+            Err(hapic::Error::HttpStatusNotSuccess {
+                status,
+                content_type,
+                body: raw_resp,
+            })
+        }
+    }
+}
+
+/// Returns the synchronous API endpoint for an API endpoint, by prefixing its host with `sync.`,
+/// for example `https://sync.api.cloudconvert.com/v2` for `https://api.cloudconvert.com/v2`.
+///
+/// Endpoints which aren't hosted at `api.*`, such as a local mock server, are returned unchanged.
+pub fn sync_endpoint(endpoint: &str) -> Cow<'_, str> {
+    match endpoint.split_once("://") {
+        Some((scheme, rest)) if rest.starts_with("api.") => {
+            Cow::Owned(format!("{scheme}://sync.{rest}"))
+        }
+        _ => Cow::Borrowed(endpoint),
+    }
+}
+
+/// Show a job, including the status of its tasks.
+///
+/// Docs: [cloudconvert.com/api/v2/jobs#jobs-show](https://cloudconvert.com/api/v2/jobs#jobs-show)
+#[derive(Debug)]
+pub struct Show<'a> {
+    /// The ID of the job.
+    pub id: Cow<'a, str>,
+}
+
+bodyless_api_call!(
+    <'a> GET Show<'a> => JobsOutput as Job,
+    |call, endpoint| format!("{endpoint}/jobs/{}", call.id)
+);
+
+/// Wait until a job has finished or failed, then show it, using the synchronous API.
+///
+/// The request doesn't return until the job has completed, so it should only be used for jobs
+/// which are expected to complete quickly. See [`crate::Client::wait_for_job`] to poll instead.
+/// Like [`CreateSync`], jobs which take too long are returned before they complete, so the
+/// [`status`](Job::status) of the returned job should be checked.
+///
+/// Docs: [cloudconvert.com/api/v2/jobs#jobs-wait](https://cloudconvert.com/api/v2/jobs#jobs-wait)
+#[derive(Debug)]
+pub struct Wait<'a> {
+    /// The ID of the job.
+    pub id: Cow<'a, str>,
+
+    /// The synchronous API endpoint. If `None`, this is derived from the client's endpoint using
+    /// [`sync_endpoint`].
+    pub endpoint: Option<Cow<'a, str>>,
+}
+
+bodyless_api_call!(
+    <'a> GET Wait<'a> => JobsOutput as Job,
+    |call, endpoint| match &call.endpoint {
+        Some(endpoint) => format!("{endpoint}/jobs/{}", call.id),
+        None => format!("{}/jobs/{}", sync_endpoint(endpoint), call.id),
+    }
+);
+
+/// Delete a job, including its tasks and files.
+///
+/// Docs: [cloudconvert.com/api/v2/jobs#jobs-delete](https://cloudconvert.com/api/v2/jobs#jobs-delete)
+#[derive(Debug)]
+pub struct Delete<'a> {
+    /// The ID of the job.
+    pub id: Cow<'a, str>,
+}
+
+bodyless_api_call!(
+    <'a> DELETE Delete<'a> => (),
+    |call, endpoint| format!("{endpoint}/jobs/{}", call.id)
+);
+
+/// List jobs, optionally filtered by status or tag.
+///
+/// Docs: [cloudconvert.com/api/v2/jobs#jobs-list](https://cloudconvert.com/api/v2/jobs#jobs-list)
+#[derive(Debug, Default)]
+pub struct List<'a> {
+    /// Only list jobs with this status.
+    pub status: Option<Status>,
+    /// Only list jobs with this tag.
+    pub tag: Option<Cow<'a, str>>,
+    /// The number of jobs per page.
+    pub per_page: Option<u32>,
+    /// The page to list, starting at 1.
+    pub page: Option<u32>,
+    /// Include the tasks of each job. Otherwise, the listed jobs have no tasks.
+    pub include_tasks: bool,
+}
+
+bodyless_api_call!(
+    <'a> GET List<'a> => JobsListOutput as Vec<Job>,
+    |call, endpoint| {
+        let per_page = call.per_page.map(|n| n.to_string());
+        let page = call.page.map(|n| n.to_string());
+        let query = crate::query_string([
+            ("filter[status]", call.status.as_ref().map(Status::as_str)),
+            ("filter[tag]", call.tag.as_deref()),
+            ("per_page", per_page.as_deref()),
+            ("page", page.as_deref()),
+            ("include", call.include_tasks.then_some("tasks")),
+        ]);
+        format!("{endpoint}/jobs{query}")
+    }
+);
+
+#[doc(hidden)]
+#[derive(Deserialize)]
+pub struct JobsListOutput {
+    #[serde(deserialize_with = "deserialize_jobs")]
+    pub data: Vec<Job>,
+}
+
+impl From<JobsListOutput> for Vec<Job> {
+    fn from(output: JobsListOutput) -> Vec<Job> {
+        output.data
+    }
+}
+
+/// An error returned by [`Job::to_create`].
+#[derive(Debug)]
+pub enum ToCreateError {
+    /// The task with this ID doesn't have a name.
+    MissingName(String),
+
+    /// The task with this ID doesn't have a payload.
+    MissingPayload(String),
+
+    /// The payload of the task with this name isn't a valid task.
+    InvalidTask(String, serde_json::Error),
+}
+
+/// If `input` is the name of an excluded task, replace it with the task ID.
+fn replace_input(input: &mut String, excluded: &HashMap<&str, &str>) {
+    if let Some(id) = excluded.get(input.as_str()) {
+        *input = id.to_string();
     }
 }
 
@@ -89,6 +428,64 @@ pub struct Job {
 }
 
 impl Job {
+    /// Rebuild the [`Create`] call for this job, from the operation and payload of each task, for
+    /// example to resubmit a failed job with some changes.
+    ///
+    /// If `exclude_finished` is `true`, tasks which have already finished aren't included. Any
+    /// task which depends on one of them refers to it by its task ID instead, so its results are
+    /// reused rather than the task being run again.
+    ///
+    /// The webhook URL isn't part of a job's status, so it isn't set.
+    pub fn to_create(&self, exclude_finished: bool) -> Result<Create<'static>, ToCreateError> {
+        let is_excluded = |task: &task::Status| exclude_finished && task.status == Status::Finished;
+        // Names of excluded tasks, mapped to their IDs.
+        let excluded: HashMap<&str, &str> = self
+            .tasks
+            .iter()
+            .filter(|task| is_excluded(task))
+            .filter_map(|task| Some((task.name.as_deref()?, task.id.as_str())))
+            .collect();
+
+        let mut tasks = HashMap::new();
+        for task in self.tasks.iter().filter(|task| !is_excluded(task)) {
+            let name = task
+                .name
+                .clone()
+                .ok_or_else(|| ToCreateError::MissingName(task.id.clone()))?;
+            let Some(payload) = task.payload.as_object() else {
+                return Err(ToCreateError::MissingPayload(task.id.clone()));
+            };
+            let mut payload = payload.clone();
+            payload.insert(
+                "operation".to_string(),
+                serde_json::Value::String(task.operation.clone()),
+            );
+            for field in ["input", "image"] {
+                match payload.get_mut(field) {
+                    Some(serde_json::Value::String(input)) => replace_input(input, &excluded),
+                    Some(serde_json::Value::Array(inputs)) => inputs
+                        .iter_mut()
+                        .filter_map(|input| match input {
+                            serde_json::Value::String(input) => Some(input),
+                            _ => None,
+                        })
+                        .for_each(|input| replace_input(input, &excluded)),
+                    _ => {}
+                }
+            }
+            let payload = serde_json::Value::Object(payload);
+            let task = serde_json::from_value(payload)
+                .map_err(|err| ToCreateError::InvalidTask(name.clone(), err))?;
+            tasks.insert(name, task);
+        }
+
+        Ok(Create {
+            tasks,
+            tag: self.tag.clone().map(Cow::Owned),
+            webhook_url: None,
+        })
+    }
+
     /// Return the task from the task name, if it exists within the job.
     ///
     /// If multiple tasks exist with the same name, this could return any of them.
@@ -97,4 +494,82 @@ impl Job {
             .iter()
             .find(|task| matches!(&task.name, Some(task_name) if task_name == name))
     }
+
+    /// Returns the files exported by every finished export task of the job, for example by an
+    /// [`task::ExportUrl`] task. The files can be downloaded using [`crate::Client::download`].
+    pub fn exported_files(&self) -> Vec<task::ExportedFile> {
+        self.tasks
+            .iter()
+            .filter(|task| task.operation.starts_with("export/") && task.status == Status::Finished)
+            .flat_map(task::Status::exported_files)
+            .collect()
+    }
+
+    /// Returns the URL of the file exported by the `export` task of a finished job, such as a
+    /// job created by [`crate::ImportConvertExport`] or [`crate::UploadConvertExport`].
+    ///
+    /// If the job didn't finish, returns an [`Error::Other`](crate::Error::Other) describing the
+    /// failed task.
+    pub fn export_url(&self) -> Result<String, crate::Error> {
+        if self.status != Some(Status::Finished) {
+            let message = match self.tasks.iter().find(|task| task.status == Status::Error) {
+                Some(task) => format!(
+                    "task {} failed ({}): {}",
+                    task.name.as_deref().unwrap_or(&task.id),
+                    task.error_code.as_deref().unwrap_or("UNKNOWN"),
+                    task.status_message.as_deref().unwrap_or("no message"),
+                ),
+                None => format!("job {} didn't finish", self.id),
+            };
+            return Err(crate::Error::Other(message.into()));
+        }
+        self.get_task_by_name("export")
+            .and_then(|task| task.exported_files().into_iter().next())
+            .and_then(|file| file.url)
+            .ok_or(crate::Error::Other("the job didn't export a file".into()))
+    }
+}
+
+/// The number of jobs with each status, and the credits they consumed, for example for a group of
+/// jobs with the same tag (see [`crate::Client::tag_summary`]).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Summary {
+    /// The total number of jobs.
+    pub jobs: usize,
+    pub waiting: usize,
+    pub processing: usize,
+    pub finished: usize,
+    pub failed: usize,
+
+    /// The total credits consumed by the tasks of the jobs.
+    pub credits: u32,
+}
+
+impl Summary {
+    /// Summarize the status of `jobs`.
+    pub fn new<'a>(jobs: impl IntoIterator<Item = &'a Job>) -> Summary {
+        let mut summary = Summary::default();
+        for job in jobs {
+            summary.jobs += 1;
+            match job.status {
+                Some(Status::Waiting) => summary.waiting += 1,
+                Some(Status::Processing) => summary.processing += 1,
+                Some(Status::Finished) => summary.finished += 1,
+                Some(Status::Error) => summary.failed += 1,
+                None => {}
+            }
+            summary.credits += job
+                .tasks
+                .iter()
+                .filter_map(|task| task.credits)
+                .map(u32::from)
+                .sum::<u32>();
+        }
+        summary
+    }
+
+    /// Returns `true` if every job has either finished or failed.
+    pub fn is_complete(&self) -> bool {
+        self.finished + self.failed == self.jobs
+    }
 }
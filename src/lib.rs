@@ -19,6 +19,12 @@
 //! Jobs can be crated using the [`job::Create`] API call. Jobs consist of a number of named
 //! [`task`]s.
 //!
+//! Jobs can also be defined by JSON or YAML templates, such as those produced by the CloudConvert
+//! job builder, using [`job::Create::from_template`].
+//!
+//! Short jobs can be created using [`job::CreateSync`] instead, which uses the synchronous API, so
+//! the job has completed by the time it's returned.
+//!
 //! ## Tasks
 //!
 //! A [`task::Task`] enum represents one of the following tasks:
@@ -26,6 +32,7 @@
 //! ### Import tasks
 //!
 //! - [`task::ImportUrl`]: Import a document from a URL.
+//! - [`task::ImportUpload`]: Create a task which a document can be uploaded to.
 //! - [`task::ImportS3`]: Import a document from an S3 compatible bucket.
 //! - [`task::ImportAzureBlob`]: Import a document from Azure Blob Storage.
 //! - [`task::ImportGoogleCloud`]: Import a document from Google Cloud Storage.
@@ -41,6 +48,15 @@
 //! - [`task::Merge`]: Merge multiple documents.
 //! - [`task::Archive`]: Create zip, rar, 7z or tar archives.
 //! - [`task::Capture`]: Capture a website.
+//! - [`task::Metadata`]: Read the metadata of a document.
+//! - [`task::WriteMetadata`]: Write metadata to a document.
+//! - [`task::PdfA`]: Convert a PDF to PDF/A.
+//! - [`task::PdfOcr`]: Add a text layer to a scanned PDF.
+//! - [`task::PdfSplitPages`]: Split a PDF into one PDF per page.
+//! - [`task::PdfExtractPages`]: Extract pages from a PDF.
+//! - [`task::PdfRotatePages`]: Rotate pages of a PDF.
+//! - [`task::PdfEncrypt`]: Encrypt a PDF and set its permissions.
+//! - [`task::PdfDecrypt`]: Remove the password from a PDF.
 //!
 //! ### Export tasks
 //!
@@ -51,29 +67,158 @@
 //! - [`task::ExportOpenStack`]: Export documents to OpenStack Object Storage (Swift).
 //! - [`task::ExportSFTP`]: Export documents to an SFTP server.
 //!
+//! The storage credentials needed by these tasks can be shared between import and export tasks
+//! using the types in the [`credentials`] module.
+//!
 //! ## Pre-made jobs
 //!
 //! - [`ImportConvertExport`]: An API call (underneath, `job::Create`) which creates a job
 //!   consisting of an import task, convert task, then export task.
+//! - [`UploadConvertExport`]: The same, but the file is uploaded, rather than imported from a
+//!   URL. [`Client::convert_file`] and [`Client::convert_bytes`] use this to convert a local file
+//!   in one call, and [`HyperClient::convert_stream`] streams large files in and out without
+//!   holding them in memory.
+//!
+//! ## Batches
+//!
+//! The [`batch::Batch`] API runs many jobs, with a bounded number in flight at once, and yields
+//! the result of each job as it completes.
+//!
+//! Jobs with the same tag can be managed as a group: [`Client::list_tagged_jobs`],
+//! [`Client::wait_for_tagged_jobs`], [`Client::cancel_tagged_jobs`],
+//! [`Client::delete_tagged_jobs`], and [`Client::tag_summary`], which counts the jobs with each
+//! status and the credits they consumed.
+//!
+//! ## Supported conversions
+//!
+//! The [`conversion::ConvertFormats`] API call lists the supported conversions, and a
+//! [`conversion::ConversionMatrix`] can be used to answer queries about them offline.
 //!
 //! ## Webhooks
 //!
 //! Tools for verifying and parsing webhooks can be found within the [`webhook`] module.
+//!
+//! ## Real-time events
+//!
+//! With the `realtime` feature enabled, the `realtime` module subscribes to the events of a job
+//! and its tasks using the Socket.io API, and yields them as a stream.
+//!
+//! ## Testing
+//!
+//! With the `testing` feature enabled, the `testing` module provides an in-process mock of the
+//! CloudConvert API, which can be used to test code using this crate without network access.
+//!
+//! ## Command line tool
+//!
+//! With the `cli` feature enabled, a `cloudconvert` binary is built, which can convert local files
+//! or URLs and download the results, show and list jobs, and list the supported conversions:
+//!
+//! ```sh
+//! export CLOUDCONVERT_TOKEN=your_bearer_token
+//! cloudconvert convert in.docx out.pdf
+//! cloudconvert job show <id>
+//! cloudconvert job list --status finished
+//! cloudconvert formats --input docx
+//! ```
 
 use std::borrow::Cow;
 use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
 
 use serde::Deserialize;
+use tokio::io::AsyncRead;
+use tokio_util::io::{ReaderStream, StreamReader};
+
+/// Implement [`ApiCall`] for a call which is made without a request body, such as a `GET` request.
+///
+/// The `uri` closure is given the call and the client endpoint. The response is parsed as
+/// `$JsonOutput`, then converted into `$Output`, or, if the output is `()`, the response body is
+/// ignored.
+macro_rules! bodyless_api_call {
+    (
+        $(<$a:lifetime>)? $method:ident $Call:ty => (),
+        |$call:ident, $endpoint:ident| $uri:expr
+    ) => {
+        impl$(<$a>)? $crate::ApiCall for $Call {}
+
+        #[async_trait::async_trait]
+        impl$(<$a>)? hapic::ApiCall for $Call {
+            type RequestBody = Vec<u8>;
+            type Output = ();
+
+            fn method(&self) -> hapic::http::Method {
+                hapic::http::Method::$method
+            }
+
+            fn uri(&self, $endpoint: &str) -> String {
+                let $call = self;
+                $uri
+            }
+
+            fn request_body(self) -> Result<Vec<u8>, hapic::Error> {
+                Ok(Vec::new())
+            }
+
+            async fn response<B: hapic::transport::ResponseBody>(
+                resp: hapic::http::Response<B>,
+            ) -> Result<(), hapic::Error> {
+                $crate::read_response(resp).await.map(drop)
+            }
+        }
+    };
+    (
+        $(<$a:lifetime>)? $method:ident $Call:ty => $JsonOutput:ty as $Output:ty,
+        |$call:ident, $endpoint:ident| $uri:expr
+    ) => {
+        impl$(<$a>)? $crate::ApiCall for $Call {}
+
+        #[async_trait::async_trait]
+        impl$(<$a>)? hapic::ApiCall for $Call {
+            type RequestBody = Vec<u8>;
+            type Output = $Output;
+
+            fn method(&self) -> hapic::http::Method {
+                hapic::http::Method::$method
+            }
+
+            fn uri(&self, $endpoint: &str) -> String {
+                let $call = self;
+                $uri
+            }
+
+            fn request_body(self) -> Result<Vec<u8>, hapic::Error> {
+                Ok(Vec::new())
+            }
+
+            async fn response<B: hapic::transport::ResponseBody>(
+                resp: hapic::http::Response<B>,
+            ) -> Result<$Output, hapic::Error> {
+                $crate::parse_json_response::<B, $JsonOutput>(resp)
+                    .await
+                    .map(<$Output>::from)
+            }
+        }
+    };
+}
 
 #[cfg(test)]
 mod tests;
 
+pub mod batch;
+pub mod conversion;
+pub mod credentials;
 mod format;
 pub mod job;
+#[cfg(feature = "realtime")]
+pub mod realtime;
+mod selection;
 pub mod task;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 pub mod webhook;
 
-pub use format::Format;
+pub use format::{Format, FormatCategory};
 use task::Task;
 
 pub use hapic::Error;
@@ -103,9 +248,367 @@ hapic::json_api!(
     json {
         <'a> "/jobs": job::Create<'a> as job::CreateJobRequest<'a> => job::JobsOutput as job::Job;
         <'a> "/jobs": ImportConvertExport<'a> as job::CreateJobRequest<'a> => job::JobsOutput as job::Job;
+        <'a> "/jobs": UploadConvertExport<'a> as job::CreateJobRequest<'a> => job::JobsOutput as job::Job;
     }
 );
 
+// The bearer token, and any extra headers (which might also contain credentials), are redacted.
+impl<B, T: hapic::transport::Transport<B>> std::fmt::Debug for Client<B, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Client")
+            .field("endpoint", &self.client.endpoint)
+            .field(
+                "authorization",
+                &self.client.authorization.as_ref().map(|_| "[REDACTED]"),
+            )
+            .field(
+                "extra_headers",
+                &self.client.extra_headers.keys().collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl<B, T> Client<B, T>
+where
+    B: From<Vec<u8>> + Send + Sync,
+    T: hapic::transport::Transport<B>,
+{
+    /// Upload a file to the form of an [`task::ImportUpload`] task.
+    ///
+    /// The form is returned by [`task::Status::upload_form`]. The bearer token isn't sent with
+    /// the upload.
+    pub async fn upload(
+        &self,
+        form: &task::UploadForm,
+        filename: &str,
+        contents: &[u8],
+    ) -> Result<(), Error> {
+        let (content_type, body) = form.multipart_body(filename, contents);
+        let req = hapic::http::Request::post(form.url.as_str())
+            .header(hapic::http::header::CONTENT_TYPE, content_type)
+            .body(B::from(body))
+            .map_err(http_error)?;
+        let resp = self.send(req).await?;
+        // The upload redirects if the task's `redirect` option is set.
+        if resp.status().is_redirection() {
+            return Ok(());
+        }
+        read_response(resp).await.map(drop)
+    }
+
+    /// Download a file, such as an exported file (see [`task::Status::exported_files`]).
+    ///
+    /// The bearer token isn't sent with the download.
+    pub async fn download(&self, url: &str) -> Result<Vec<u8>, Error> {
+        let req = hapic::http::Request::get(url)
+            .body(B::from(Vec::new()))
+            .map_err(http_error)?;
+        read_response(self.send(req).await?).await
+    }
+
+    /// Poll a job, using [`job::Show`], until it has either finished or failed.
+    ///
+    /// The job is fetched once every `interval`. Whether the job finished or failed should be
+    /// checked using [`job::Job::status`].
+    pub async fn wait_for_job(&self, id: &str, interval: Duration) -> Result<job::Job, Error> {
+        loop {
+            let job = self.call(job::Show { id: id.into() }).await?;
+            if matches!(job.status, Some(Status::Finished | Status::Error)) {
+                return Ok(job);
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    /// List every job, optionally filtered by status or tag, including their tasks, fetching as
+    /// many pages of [`job::List`] as needed.
+    pub async fn list_all_jobs(
+        &self,
+        status: Option<Status>,
+        tag: Option<&str>,
+    ) -> Result<Vec<job::Job>, Error> {
+        let mut jobs = Vec::new();
+        for page in 1.. {
+            let page = self
+                .call(job::List {
+                    status,
+                    tag: tag.map(Into::into),
+                    per_page: Some(LIST_PAGE_SIZE),
+                    page: Some(page),
+                    include_tasks: true,
+                })
+                .await?;
+            let last = page.len() < LIST_PAGE_SIZE as usize;
+            jobs.extend(page);
+            if last {
+                break;
+            }
+        }
+        Ok(jobs)
+    }
+
+    /// List every job with the given tag, including their tasks, fetching as many pages of
+    /// [`job::List`] as needed.
+    pub async fn list_tagged_jobs(&self, tag: &str) -> Result<Vec<job::Job>, Error> {
+        self.list_all_jobs(None, Some(tag)).await
+    }
+
+    /// Summarize the status and credits of the jobs with the given tag.
+    pub async fn tag_summary(&self, tag: &str) -> Result<job::Summary, Error> {
+        Ok(job::Summary::new(&self.list_tagged_jobs(tag).await?))
+    }
+
+    /// Poll the jobs with the given tag, until every one of them has either finished or failed.
+    ///
+    /// The jobs are listed once every `interval`, so jobs created with the tag while waiting are
+    /// also waited for. Returns the completed jobs, which can be summarized with
+    /// [`job::Summary::new`].
+    pub async fn wait_for_tagged_jobs(
+        &self,
+        tag: &str,
+        interval: Duration,
+    ) -> Result<Vec<job::Job>, Error> {
+        loop {
+            let jobs = self.list_tagged_jobs(tag).await?;
+            if job::Summary::new(&jobs).is_complete() {
+                return Ok(jobs);
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    /// Cancel every job with the given tag which hasn't finished or failed, by cancelling its
+    /// waiting and processing tasks using [`task::Cancel`]. The cancelled jobs fail.
+    ///
+    /// Returns the number of jobs which were cancelled, which doesn't include jobs without any
+    /// tasks left to cancel.
+    pub async fn cancel_tagged_jobs(&self, tag: &str) -> Result<usize, Error> {
+        let mut cancelled = 0;
+        for job in self.list_tagged_jobs(tag).await? {
+            if matches!(job.status, Some(Status::Finished | Status::Error)) {
+                continue;
+            }
+            let mut any_cancelled = false;
+            for task in &job.tasks {
+                if matches!(task.status, Status::Waiting | Status::Processing) {
+                    self.call(task::Cancel {
+                        id: task.id.as_str().into(),
+                    })
+                    .await?;
+                    any_cancelled = true;
+                }
+            }
+            if any_cancelled {
+                cancelled += 1;
+            }
+        }
+        Ok(cancelled)
+    }
+
+    /// Delete every job with the given tag, including jobs which haven't finished yet.
+    ///
+    /// Returns the number of jobs which were deleted.
+    pub async fn delete_tagged_jobs(&self, tag: &str) -> Result<usize, Error> {
+        let jobs = self.list_tagged_jobs(tag).await?;
+        for job in &jobs {
+            self.call(job::Delete {
+                id: job.id.as_str().into(),
+            })
+            .await?;
+        }
+        Ok(jobs.len())
+    }
+
+    /// Convert a local file to `output_format`, returning the converted file.
+    ///
+    /// The input format is inferred from the file extension, if possible, otherwise CloudConvert
+    /// detects it. See [`Client::convert_bytes`] for details of how the file is converted.
+    pub async fn convert_file(
+        &self,
+        path: impl AsRef<Path>,
+        output_format: Format,
+    ) -> Result<Vec<u8>, Error> {
+        let path = path.as_ref();
+        let contents = tokio::fs::read(path).await.map_err(|err| {
+            Error::Other(format!("couldn't read {}: {err}", path.display()).into())
+        })?;
+        let filename = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("file");
+        self.convert_upload(filename, &contents, Format::from_path(path), output_format)
+            .await
+    }
+
+    /// Convert `contents` from `input_format` to `output_format`, returning the converted file.
+    ///
+    /// This creates an [`UploadConvertExport`] job, uploads the file, waits for the job using
+    /// [`job::Wait`], downloads the result, then deletes the job. If the synchronous API returns
+    /// before the job has completed, the job is polled every second. If the job fails, or hasn't
+    /// completed after an hour, an [`Error::Other`] is returned. Failing to delete the job isn't
+    /// treated as an error, since CloudConvert deletes jobs automatically after 24 hours.
+    pub async fn convert_bytes(
+        &self,
+        contents: &[u8],
+        input_format: Format,
+        output_format: Format,
+    ) -> Result<Vec<u8>, Error> {
+        let filename = format!("file.{}", input_format.str());
+        self.convert_upload(&filename, contents, Some(input_format), output_format)
+            .await
+    }
+
+    async fn convert_upload(
+        &self,
+        filename: &str,
+        contents: &[u8],
+        input_format: Option<Format>,
+        output_format: Format,
+    ) -> Result<Vec<u8>, Error> {
+        let job = self
+            .call(UploadConvertExport {
+                tag: None,
+                input_format,
+                output_format,
+                timeout: None,
+                webhook_url: None,
+            })
+            .await?;
+        let result = self.upload_and_download(&job, filename, contents).await;
+        self.call(job::Delete {
+            id: job.id.as_str().into(),
+        })
+        .await
+        .ok();
+        result
+    }
+
+    async fn upload_and_download(
+        &self,
+        job: &job::Job,
+        filename: &str,
+        contents: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+        let form = upload_form(job)?;
+        self.upload(&form, filename, contents).await?;
+        let job = self
+            .wait_sync(&job.id, CONVERT_POLL_INTERVAL, CONVERT_TIMEOUT)
+            .await?;
+        self.download(&job.export_url()?).await
+    }
+
+    /// Wait for a job to finish or fail using [`job::Wait`]. If the synchronous API returns
+    /// before the job has completed, the job is polled using [`Client::wait_for_job`] every
+    /// `interval`. If the job hasn't completed within `timeout`, an [`Error::Other`] is returned.
+    async fn wait_sync(
+        &self,
+        id: &str,
+        interval: Duration,
+        timeout: Duration,
+    ) -> Result<job::Job, Error> {
+        let wait = async {
+            let job = self
+                .call(job::Wait {
+                    id: id.into(),
+                    endpoint: None,
+                })
+                .await?;
+            if matches!(job.status, Some(Status::Finished | Status::Error)) {
+                return Ok(job);
+            }
+            self.wait_for_job(id, interval).await
+        };
+        tokio::time::timeout(timeout, wait)
+            .await
+            .map_err(|_| Error::Other(format!("timed out waiting for job {id}").into()))?
+    }
+
+    async fn send(
+        &self,
+        req: hapic::http::Request<B>,
+    ) -> Result<hapic::http::Response<T::ResponseBody>, Error> {
+        self.client.transport.request(req).await.map_err(Into::into)
+    }
+}
+
+/// How often the convert helpers poll a job which the synchronous API returned unfinished.
+const CONVERT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long the convert helpers wait for a job to complete.
+const CONVERT_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+/// The number of jobs fetched per page by [`Client::list_all_jobs`].
+const LIST_PAGE_SIZE: u32 = 100;
+
+/// Returns the upload form of the `import` task of an [`UploadConvertExport`] job.
+fn upload_form(job: &job::Job) -> Result<task::UploadForm, Error> {
+    job.get_task_by_name("import")
+        .and_then(task::Status::upload_form)
+        .ok_or(Error::Other("the import task has no upload form".into()))
+}
+
+fn http_error(err: hapic::http::Error) -> Error {
+    Error::Other(err.to_string().into())
+}
+
+/// Read a response body, returning an error if the request wasn't successful.
+async fn read_response<B: hapic::transport::ResponseBody>(
+    resp: hapic::http::Response<B>,
+) -> Result<Vec<u8>, Error> {
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get(hapic::http::header::CONTENT_TYPE)
+        .cloned();
+    let body = resp.into_body().read_all().await.map_err(Into::into)?;
+    if status.is_success() {
+        Ok(body.into())
+    } else {
+        Err(Error::HttpStatusNotSuccess {
+            status,
+            content_type,
+            body: body.into(),
+        })
+    }
+}
+
+/// Read a response body, and parse it as JSON if the request was successful.
+async fn parse_json_response<B: hapic::transport::ResponseBody, O: for<'de> Deserialize<'de>>(
+    resp: hapic::http::Response<B>,
+) -> Result<O, Error> {
+    let body = read_response(resp).await?;
+    Ok(serde_json::from_slice(&body)?)
+}
+
+/// Percent-encode `s`, for use in a URL path segment or query string, appending it to `out`.
+fn percent_encode(s: &str, out: &mut String) {
+    for byte in s.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(byte as char)
+            }
+            _ => out.push_str(&format!("%{byte:02X}")),
+        }
+    }
+}
+
+/// Build a query string (including the leading `?`) from a list of optional parameters.
+///
+/// Parameters with a value of `None` are skipped. If there are no parameters, the string is empty.
+fn query_string<'a>(params: impl IntoIterator<Item = (&'a str, Option<&'a str>)>) -> String {
+    let mut query = String::new();
+    for (name, value) in params {
+        if let Some(value) = value {
+            query.push(if query.is_empty() { '?' } else { '&' });
+            percent_encode(name, &mut query);
+            query.push('=');
+            percent_encode(value, &mut query);
+        }
+    }
+    query
+}
+
 /// A [`Client`] using [`hyper`] for transport.
 pub type HyperClient = Client<hyper::Body, hapic::transport::HttpsTransport>;
 
@@ -116,10 +619,105 @@ impl HyperClient {
             Some(("Bearer ".to_string() + bearer_token).try_into().unwrap());
         client
     }
+
+    /// Convert a file read from `reader`, from `input_format` to `output_format`, returning a
+    /// reader of the converted file.
+    ///
+    /// Like [`Client::convert_bytes`], this creates an [`UploadConvertExport`] job, but the file is
+    /// streamed to the upload form as it's read, and the converted file is streamed from the
+    /// export URL as the returned reader is read, so neither file is held in memory. Since the
+    /// size of the file isn't known, it's uploaded using chunked transfer encoding.
+    ///
+    /// The job isn't deleted, since the converted file is still being downloaded when this
+    /// returns. CloudConvert deletes jobs automatically after 24 hours.
+    pub async fn convert_stream<R>(
+        &self,
+        reader: R,
+        input_format: Format,
+        output_format: Format,
+    ) -> Result<impl AsyncRead + Send + Unpin, Error>
+    where
+        R: AsyncRead + Send + 'static,
+    {
+        use futures_util::{stream, StreamExt, TryStreamExt};
+        use hapic::http::{header, Request};
+
+        let job = self
+            .call(UploadConvertExport {
+                tag: None,
+                input_format: Some(input_format.clone()),
+                output_format,
+                timeout: None,
+                webhook_url: None,
+            })
+            .await?;
+
+        let form = upload_form(&job)?;
+        let boundary = stream_boundary();
+        let filename = format!("file.{}", input_format.str());
+        let (head, tail) = form.multipart_parts(&filename, &boundary);
+        let body = stream::once(async { Ok(head.into()) })
+            .chain(ReaderStream::with_capacity(reader, STREAM_CHUNK_SIZE))
+            .chain(stream::once(async { Ok(tail.into()) }));
+        let req = Request::post(form.url.as_str())
+            .header(
+                header::CONTENT_TYPE,
+                format!("multipart/form-data; boundary={boundary}"),
+            )
+            .body(hyper::Body::wrap_stream::<
+                _,
+                hyper::body::Bytes,
+                std::io::Error,
+            >(body))
+            .map_err(http_error)?;
+        let resp = self.send(req).await?;
+        // The upload redirects if the task's `redirect` option is set.
+        if !resp.status().is_redirection() {
+            read_response(resp).await?;
+        }
+
+        let job = self
+            .wait_sync(&job.id, CONVERT_POLL_INTERVAL, CONVERT_TIMEOUT)
+            .await?;
+        let req = Request::get(job.export_url()?)
+            .body(hyper::Body::empty())
+            .map_err(http_error)?;
+        let resp = self.send(req).await?;
+        if !resp.status().is_success() {
+            // This reads the body of the error response, and always returns an error.
+            read_response(resp).await?;
+            return Err(Error::HttpNoBody);
+        }
+        let body = resp.into_body().map_err(std::io::Error::other);
+        Ok(StreamReader::new(body))
+    }
+}
+
+/// The size of the chunks read from the reader passed to [`HyperClient::convert_stream`].
+const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Returns a multipart boundary for a streamed upload.
+///
+/// The contents aren't known in advance, so, unlike [`task::UploadForm::multipart_body`], the
+/// boundary can't be derived from them. Instead it's derived from the time and a counter, so it's
+/// vanishingly unlikely to appear in the file.
+fn stream_boundary() -> String {
+    use sha2::Digest;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let mut hasher = sha2::Sha256::new();
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default();
+    hasher.update(now.as_nanos().to_le_bytes());
+    hasher.update(COUNTER.fetch_add(1, Ordering::Relaxed).to_le_bytes());
+    hasher.update(std::process::id().to_le_bytes());
+    format!("cloudconvert-rs-{}", hex::encode(&hasher.finalize()[..16]))
 }
 
 /// Status of a [`job::Job`] or [`task::Status`].
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
 pub enum Status {
     #[serde(rename = "waiting")]
     Waiting,
@@ -131,6 +729,18 @@ pub enum Status {
     Error,
 }
 
+impl Status {
+    /// The name of the status, as used by the API.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Status::Waiting => "waiting",
+            Status::Processing => "processing",
+            Status::Finished => "finished",
+            Status::Error => "error",
+        }
+    }
+}
+
 /// An API call, which underneath converts to a [`job::Create`] call, to import from a URL,
 /// convert, and then export to a URL.
 #[derive(Debug)]
@@ -181,10 +791,12 @@ impl<'a> ImportConvertExport<'a> {
                         input: import_id.into(),
                         input_format: Some(self.input_format),
                         output_format: self.output_format,
+                        options: None,
                         filename: None,
                         engine: None,
                         engine_version: None,
                         timeout: self.timeout,
+                        extra: Default::default(),
                     }
                     .into(),
                 ),
@@ -194,6 +806,88 @@ impl<'a> ImportConvertExport<'a> {
                         input: convert_id.into(),
                         inline: self.export_inline,
                         archive_multiple_files: false,
+                        extra: Default::default(),
+                    }
+                    .into(),
+                ),
+            ] as [(String, Task); 3]),
+            tag: self.tag,
+            webhook_url: self.webhook_url,
+        }
+    }
+}
+
+/// An API call, which underneath converts to a [`job::Create`] call, to create an upload task,
+/// convert the uploaded file, and then export it to a URL.
+///
+/// The file should be uploaded to the form of the `import` task (see [`Client::upload`]), and the
+/// result is exported by the `export` task. [`Client::convert_bytes`] and [`Client::convert_file`]
+/// do all of this.
+#[derive(Debug)]
+pub struct UploadConvertExport<'a> {
+    /// The tag to apply to the created job.
+    pub tag: Option<Cow<'a, str>>,
+
+    /// The input format to convert from. If this isn't set, it's detected by CloudConvert.
+    pub input_format: Option<Format>,
+
+    /// The format to convert to.
+    pub output_format: Format,
+
+    pub timeout: Option<u32>,
+    pub webhook_url: Option<Cow<'a, str>>,
+}
+
+impl<'a> From<UploadConvertExport<'a>> for job::Create<'a> {
+    fn from(options: UploadConvertExport<'a>) -> job::Create<'a> {
+        options.create_job()
+    }
+}
+
+impl<'a> From<UploadConvertExport<'a>> for job::CreateJobRequest<'a> {
+    fn from(options: UploadConvertExport<'a>) -> job::CreateJobRequest<'a> {
+        options.create_job().into()
+    }
+}
+
+impl<'a> UploadConvertExport<'a> {
+    /// Convert `self` to a [`job::Create`] call.
+    pub fn create_job(self) -> job::Create<'a> {
+        let import_id = "import";
+        let convert_id = "convert";
+        let export_id = "export";
+        job::Create {
+            tasks: HashMap::from([
+                (
+                    import_id.to_string(),
+                    task::ImportUpload {
+                        redirect: None,
+                        extra: Default::default(),
+                    }
+                    .into(),
+                ),
+                (
+                    convert_id.to_string(),
+                    task::Convert {
+                        input: import_id.into(),
+                        input_format: self.input_format,
+                        output_format: self.output_format,
+                        options: None,
+                        filename: None,
+                        engine: None,
+                        engine_version: None,
+                        timeout: self.timeout,
+                        extra: Default::default(),
+                    }
+                    .into(),
+                ),
+                (
+                    export_id.to_string(),
+                    task::ExportUrl {
+                        input: convert_id.into(),
+                        inline: false,
+                        archive_multiple_files: false,
+                        extra: Default::default(),
                     }
                     .into(),
                 ),
@@ -0,0 +1,307 @@
+//! Real-time job and task events, using the CloudConvert Socket.io API.
+//!
+//! This requires the `realtime` feature. [`Realtime::subscribe_job`] connects to the API,
+//! subscribes to the events of a job and its tasks, and returns a stream of [`Event`]s, which ends
+//! once the job has finished or failed:
+//!
+//! ```no_run
+//! use cloudconvert::realtime::Realtime;
+//! use futures_util::StreamExt;
+//!
+//! # async fn example(job: cloudconvert::job::Job) -> Result<(), cloudconvert::realtime::Error> {
+//! let realtime = Realtime::new("your_bearer_token");
+//! let mut events = realtime.subscribe_job(&job.id).await?;
+//! while let Some(event) = events.next().await {
+//!     let event = event?;
+//!     if let Some(task) = &event.task {
+//!         println!("{:?}: task {} is {}", event.event, task.id, task.status.as_str());
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Events which happen before the subscription is made aren't received, so a job which may
+//! already have progressed should be checked using [`job::Show`](crate::job::Show) after
+//! subscribing.
+//!
+//! Docs: [cloudconvert.com/api/v2/socket-api](https://cloudconvert.com/api/v2/socket-api)
+
+use std::borrow::Cow;
+use std::time::Duration;
+
+use futures_util::stream::BoxStream;
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use tokio::net::TcpStream;
+use tokio::time::Instant;
+use tokio_tungstenite::tungstenite::{self, Message};
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+use crate::job::{self, Job};
+use crate::task;
+use crate::webhook::EventKind;
+
+/// The URL of the CloudConvert Socket.io API, using the websocket transport.
+pub const DEFAULT_URL: &str =
+    "wss://socketio.cloudconvert.com/socket.io/?EIO=3&transport=websocket";
+
+/// A connection to the Socket.io API. See the [module docs](self).
+pub struct Realtime<'a> {
+    /// The Socket.io URL, using the websocket transport. See [`DEFAULT_URL`].
+    pub url: Cow<'a, str>,
+
+    /// The API token, which is used to authorize each subscription.
+    pub token: Cow<'a, str>,
+}
+
+// The token is redacted.
+impl std::fmt::Debug for Realtime<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Realtime")
+            .field("url", &self.url)
+            .field("token", &"[REDACTED]")
+            .finish()
+    }
+}
+
+/// A job or task event.
+///
+/// Job events (such as [`EventKind::JobFinished`]) have a `job`, and task events (such as
+/// [`EventKind::TaskUpdated`]) have a `task`.
+#[derive(Debug)]
+pub struct Event {
+    /// The kind of event.
+    pub event: EventKind,
+
+    /// The job, for job events.
+    pub job: Option<Job>,
+
+    /// The task, for task events.
+    pub task: Option<task::Status>,
+}
+
+/// An error returned by [`Realtime::subscribe_job`], or yielded by its stream.
+#[derive(Debug)]
+pub enum Error {
+    /// The websocket connection failed.
+    WebSocket(tungstenite::Error),
+
+    /// An event couldn't be parsed.
+    Json(serde_json::Error),
+
+    /// The server sent something other than a Socket.io packet, or a Socket.io error.
+    Protocol(String),
+
+    /// The server refused a subscription, for example because the token is invalid.
+    Subscription(serde_json::Value),
+
+    /// The connection closed before the job finished or failed.
+    Closed,
+}
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// The state of a subscription stream.
+struct Connection {
+    socket: Socket,
+    ping_interval: Duration,
+    next_ping: Instant,
+    done: bool,
+}
+
+/// A Socket.io packet, received over Engine.IO.
+enum Packet {
+    Open { ping_interval: Duration },
+    Ping,
+    Event(Box<Event>),
+    Other,
+}
+
+impl<'a> Realtime<'a> {
+    /// Connect to [`DEFAULT_URL`] using `token`.
+    pub fn new(token: impl Into<Cow<'a, str>>) -> Realtime<'a> {
+        Realtime {
+            url: DEFAULT_URL.into(),
+            token: token.into(),
+        }
+    }
+
+    /// Connect, and subscribe to the events of a job and its tasks.
+    ///
+    /// Returns a stream of the events, which ends after the job finishes or fails. The connection
+    /// is closed when the stream ends or is dropped. Events with kinds other than [`EventKind`]
+    /// are ignored.
+    pub async fn subscribe_job(
+        &self,
+        job_id: &str,
+    ) -> Result<BoxStream<'static, Result<Event, Error>>, Error> {
+        let (mut socket, _) = tokio_tungstenite::connect_async(self.url.as_ref())
+            .await
+            .map_err(Error::WebSocket)?;
+
+        let ping_interval = loop {
+            let text = next_text(&mut socket).await?.ok_or(Error::Closed)?;
+            if let Packet::Open { ping_interval } = parse_packet(&text)? {
+                break ping_interval;
+            }
+        };
+
+        let authorization = format!("Bearer {}", self.token);
+        for channel in [
+            format!("private-job.{job_id}"),
+            format!("private-job.{job_id}.tasks"),
+        ] {
+            let subscribe = serde_json::json!([
+                "subscribe",
+                {
+                    "channel": channel,
+                    "auth": { "headers": { "Authorization": authorization } },
+                },
+            ]);
+            send_text(&mut socket, format!("42{subscribe}")).await?;
+        }
+
+        let connection = Connection {
+            socket,
+            ping_interval,
+            next_ping: Instant::now() + ping_interval,
+            done: false,
+        };
+        Ok(futures_util::stream::unfold(connection, next_event).boxed())
+    }
+}
+
+/// Receive the next event, replying to pings and sending our own while waiting.
+async fn next_event(mut conn: Connection) -> Option<(Result<Event, Error>, Connection)> {
+    if conn.done {
+        conn.socket.close(None).await.ok();
+        return None;
+    }
+    loop {
+        let text = match tokio::time::timeout_at(conn.next_ping, next_text(&mut conn.socket)).await
+        {
+            Ok(Ok(Some(text))) => text,
+            Ok(Ok(None)) => return conn.fail(Error::Closed),
+            Ok(Err(err)) => return conn.fail(err),
+            // Engine.IO v3 clients send the pings, and the server replies.
+            Err(_) => {
+                conn.next_ping = Instant::now() + conn.ping_interval;
+                match send_text(&mut conn.socket, "2".to_string()).await {
+                    Ok(()) => continue,
+                    Err(err) => return conn.fail(err),
+                }
+            }
+        };
+        match parse_packet(&text) {
+            Ok(Packet::Event(event)) => {
+                conn.done = matches!(event.event, EventKind::JobFinished | EventKind::JobFailed);
+                return Some((Ok(*event), conn));
+            }
+            Ok(Packet::Ping) => {
+                if let Err(err) = send_text(&mut conn.socket, "3".to_string()).await {
+                    return conn.fail(err);
+                }
+            }
+            Ok(Packet::Open { .. } | Packet::Other) => {}
+            Err(err) => return conn.fail(err),
+        }
+    }
+}
+
+impl Connection {
+    /// Yield an error, then end the stream.
+    fn fail(mut self, err: Error) -> Option<(Result<Event, Error>, Connection)> {
+        self.done = true;
+        Some((Err(err), self))
+    }
+}
+
+/// Receive the next text message, or `None` if the connection has closed.
+async fn next_text(socket: &mut Socket) -> Result<Option<String>, Error> {
+    while let Some(message) = socket.next().await {
+        match message.map_err(Error::WebSocket)? {
+            Message::Text(text) => return Ok(Some(text.to_string())),
+            Message::Close(_) => return Ok(None),
+            _ => {}
+        }
+    }
+    Ok(None)
+}
+
+async fn send_text(socket: &mut Socket, text: String) -> Result<(), Error> {
+    socket
+        .send(Message::text(text))
+        .await
+        .map_err(Error::WebSocket)
+}
+
+/// Parse an Engine.IO packet, and the Socket.io packet within it.
+fn parse_packet(text: &str) -> Result<Packet, Error> {
+    let protocol_error = || Error::Protocol(format!("unexpected packet: {text}"));
+    let mut chars = text.chars();
+    match chars.next() {
+        Some('0') => {
+            #[derive(Deserialize)]
+            #[serde(rename_all = "camelCase")]
+            struct Open {
+                ping_interval: u64,
+            }
+            let open: Open = serde_json::from_str(chars.as_str()).map_err(Error::Json)?;
+            Ok(Packet::Open {
+                ping_interval: Duration::from_millis(open.ping_interval),
+            })
+        }
+        Some('1') => Err(Error::Closed),
+        Some('2') => Ok(Packet::Ping),
+        Some('3') | Some('6') => Ok(Packet::Other),
+        Some('4') => match chars.next() {
+            // Connect and acknowledgement packets.
+            Some('0') | Some('3') => Ok(Packet::Other),
+            Some('1') => Err(Error::Closed),
+            Some('2') => parse_event(chars.as_str()),
+            Some('4') => Err(Error::Protocol(format!(
+                "socket.io error: {}",
+                chars.as_str()
+            ))),
+            _ => Err(protocol_error()),
+        },
+        _ => Err(protocol_error()),
+    }
+}
+
+/// Parse a Socket.io event packet, which is a JSON array of the event name followed by its
+/// arguments. The arguments are the channel, then the event data.
+fn parse_event(json: &str) -> Result<Packet, Error> {
+    let args: Vec<serde_json::Value> = serde_json::from_str(json).map_err(Error::Json)?;
+    let Some((name, args)) = args.split_first() else {
+        return Ok(Packet::Other);
+    };
+    if name == "subscription_error" {
+        return Err(Error::Subscription(
+            args.last().cloned().unwrap_or_default(),
+        ));
+    }
+    let Ok(event) = EventKind::deserialize(name) else {
+        return Ok(Packet::Other);
+    };
+
+    #[derive(Deserialize)]
+    struct Data {
+        #[serde(default)]
+        job: Option<Job>,
+        #[serde(default)]
+        task: Option<task::Status>,
+    }
+    let data = args.last().cloned().unwrap_or_default();
+    let data = Data::deserialize(data).map_err(Error::Json)?;
+    Ok(Packet::Event(Box::new(Event {
+        event,
+        job: data
+            .job
+            .map(job::fill_task_job_ids)
+            .transpose()
+            .map_err(Error::Protocol)?,
+        task: data.task,
+    })))
+}
@@ -0,0 +1,334 @@
+//! Types for the page selectors and timestamps used by tasks. These are re-exported from
+//! [`crate::task`].
+
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// A selection of pages from a document, for example `1-3,5`, or all pages.
+///
+/// Page numbers start at 1. A selection can be built using [`PageSelection::all`],
+/// [`PageSelection::page`], [`PageSelection::range`] and [`PageSelection::and_range`], or parsed
+/// from a string using [`str::parse`]. Either way, it's validated on construction.
+///
+/// ```
+/// use cloudconvert::task::PageSelection;
+///
+/// let pages = PageSelection::range(1..=3).unwrap().and_page(5).unwrap();
+/// assert_eq!(pages.to_string(), "1-3,5");
+/// assert_eq!("1-3,5".parse::<PageSelection>().unwrap(), pages);
+/// assert!("3-1".parse::<PageSelection>().is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PageSelection {
+    /// The selected ranges, or `None` if all pages are selected.
+    ranges: Option<Vec<RangeInclusive<u32>>>,
+}
+
+/// An error returned when constructing or parsing a [`PageSelection`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum PageSelectionError {
+    /// No pages were selected.
+    Empty,
+
+    /// A page number was 0. Page numbers start at 1.
+    ZeroPage,
+
+    /// The start of a range was after the end.
+    ReversedRange { start: u32, end: u32 },
+
+    /// A page number in the string couldn't be parsed.
+    InvalidNumber(String),
+}
+
+impl PageSelection {
+    /// Select every page.
+    pub fn all() -> PageSelection {
+        PageSelection { ranges: None }
+    }
+
+    /// Select a single page.
+    pub fn page(page: u32) -> Result<PageSelection, PageSelectionError> {
+        PageSelection::range(page..=page)
+    }
+
+    /// Select an inclusive range of pages.
+    pub fn range(range: RangeInclusive<u32>) -> Result<PageSelection, PageSelectionError> {
+        PageSelection {
+            ranges: Some(vec![]),
+        }
+        .and_range(range)
+    }
+
+    /// Select a list of pages.
+    pub fn pages(
+        pages: impl IntoIterator<Item = u32>,
+    ) -> Result<PageSelection, PageSelectionError> {
+        let mut selection = PageSelection {
+            ranges: Some(vec![]),
+        };
+        for page in pages {
+            selection = selection.and_page(page)?;
+        }
+        selection.check_not_empty()
+    }
+
+    /// Add a single page to the selection.
+    pub fn and_page(self, page: u32) -> Result<PageSelection, PageSelectionError> {
+        self.and_range(page..=page)
+    }
+
+    /// Add an inclusive range of pages to the selection.
+    ///
+    /// If all pages are already selected, the selection is unchanged.
+    pub fn and_range(
+        mut self,
+        range: RangeInclusive<u32>,
+    ) -> Result<PageSelection, PageSelectionError> {
+        let (start, end) = (*range.start(), *range.end());
+        if start == 0 {
+            return Err(PageSelectionError::ZeroPage);
+        }
+        if start > end {
+            return Err(PageSelectionError::ReversedRange { start, end });
+        }
+        if let Some(ranges) = self.ranges.as_mut() {
+            ranges.push(range);
+        }
+        Ok(self)
+    }
+
+    /// Returns `true` if every page is selected.
+    pub fn is_all(&self) -> bool {
+        self.ranges.is_none()
+    }
+
+    /// Returns `true` if `page` is selected.
+    pub fn contains(&self, page: u32) -> bool {
+        match &self.ranges {
+            None => page != 0,
+            Some(ranges) => ranges.iter().any(|range| range.contains(&page)),
+        }
+    }
+
+    fn check_not_empty(self) -> Result<PageSelection, PageSelectionError> {
+        match &self.ranges {
+            Some(ranges) if ranges.is_empty() => Err(PageSelectionError::Empty),
+            _ => Ok(self),
+        }
+    }
+}
+
+impl fmt::Display for PageSelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(ranges) = &self.ranges else {
+            return f.write_str("all");
+        };
+        for (idx, range) in ranges.iter().enumerate() {
+            if idx != 0 {
+                f.write_str(",")?;
+            }
+            if range.start() == range.end() {
+                write!(f, "{}", range.start())?;
+            } else {
+                write!(f, "{}-{}", range.start(), range.end())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for PageSelection {
+    type Err = PageSelectionError;
+
+    fn from_str(s: &str) -> Result<PageSelection, PageSelectionError> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("all") {
+            return Ok(PageSelection::all());
+        }
+
+        fn parse_page(page: &str) -> Result<u32, PageSelectionError> {
+            let page = page.trim();
+            page.parse()
+                .map_err(|_| PageSelectionError::InvalidNumber(page.to_string()))
+        }
+
+        let mut selection = PageSelection {
+            ranges: Some(vec![]),
+        };
+        for item in s.split(',').filter(|item| !item.trim().is_empty()) {
+            selection = match item.split_once('-') {
+                Some((start, end)) => selection.and_range(parse_page(start)?..=parse_page(end)?)?,
+                None => selection.and_page(parse_page(item)?)?,
+            };
+        }
+        selection.check_not_empty()
+    }
+}
+
+// Serialize to the string value
+impl Serialize for PageSelection {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+// Deserialize, and validate, from the string value
+impl<'de> Deserialize<'de> for PageSelection {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<PageSelection, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(|err| {
+            serde::de::Error::custom(format!("invalid page selection {s:?}: {err:?}"))
+        })
+    }
+}
+
+/// A timestamp within a video or audio file.
+///
+/// This can be created from a [`Duration`], or parsed from a string, either as a number of
+/// seconds (`5` or `5.5`), or as `HH:MM:SS`, `MM:SS` or `HH:MM:SS.mmm`. It's serialized as
+/// `HH:MM:SS`, with milliseconds if they're non-zero.
+///
+/// ```
+/// use cloudconvert::task::Timestamp;
+///
+/// let timestamp: Timestamp = "1:05".parse().unwrap();
+/// assert_eq!(timestamp, Timestamp::from_secs(65));
+/// assert_eq!(timestamp.to_string(), "00:01:05");
+/// assert!("00:61:00".parse::<Timestamp>().is_err());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Timestamp(Duration);
+
+/// An error returned when parsing a [`Timestamp`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum TimestampError {
+    /// A component of the timestamp couldn't be parsed.
+    InvalidNumber(String),
+
+    /// The minutes or seconds component of a `HH:MM:SS` timestamp was 60 or more.
+    OutOfRange(String),
+
+    /// There were more than three `:` separated components.
+    TooManyComponents,
+
+    /// The timestamp was too large to be represented as a [`Duration`].
+    Overflow(String),
+}
+
+impl Timestamp {
+    pub fn from_secs(secs: u64) -> Timestamp {
+        Timestamp(Duration::from_secs(secs))
+    }
+
+    pub fn from_millis(millis: u64) -> Timestamp {
+        Timestamp(Duration::from_millis(millis))
+    }
+
+    /// Returns the timestamp as a duration from the start of the file.
+    pub fn as_duration(&self) -> Duration {
+        self.0
+    }
+}
+
+impl From<Duration> for Timestamp {
+    fn from(duration: Duration) -> Timestamp {
+        Timestamp(duration)
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let secs = self.0.as_secs();
+        write!(
+            f,
+            "{:02}:{:02}:{:02}",
+            secs / 3600,
+            secs / 60 % 60,
+            secs % 60
+        )?;
+        let millis = self.0.subsec_millis();
+        if millis != 0 {
+            write!(f, ".{millis:03}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = TimestampError;
+
+    fn from_str(s: &str) -> Result<Timestamp, TimestampError> {
+        let s = s.trim();
+        let components: Vec<&str> = s.split(':').collect();
+        if components.len() > 3 {
+            return Err(TimestampError::TooManyComponents);
+        }
+
+        // Everything except the seconds must be a whole number.
+        let (seconds, whole) = components.split_last().unwrap();
+        let overflow = || TimestampError::Overflow(s.to_string());
+        let mut total = Duration::ZERO;
+        for (idx, component) in whole.iter().enumerate() {
+            let value: u64 = component
+                .parse()
+                .map_err(|_| TimestampError::InvalidNumber(component.to_string()))?;
+            // Only the hours (the first of three components) may be 60 or more.
+            if value >= 60 && !(idx == 0 && whole.len() == 2) {
+                return Err(TimestampError::OutOfRange(s.to_string()));
+            }
+            total = total
+                .checked_mul(60)
+                .and_then(|total| total.checked_add(Duration::from_secs(value)))
+                .ok_or_else(overflow)?;
+        }
+
+        let invalid_seconds = || TimestampError::InvalidNumber(seconds.to_string());
+        if seconds.is_empty() || !seconds.bytes().all(|b| b.is_ascii_digit() || b == b'.') {
+            return Err(invalid_seconds());
+        }
+        let seconds = Duration::try_from_secs_f64(seconds.parse().map_err(|_| invalid_seconds())?)
+            .map_err(|_| invalid_seconds())?;
+        if !whole.is_empty() && seconds >= Duration::from_secs(60) {
+            return Err(TimestampError::OutOfRange(s.to_string()));
+        }
+        total
+            .checked_mul(60)
+            .and_then(|total| total.checked_add(seconds))
+            .map(Timestamp)
+            .ok_or_else(overflow)
+    }
+}
+
+// Serialize to the `HH:MM:SS` string value
+impl Serialize for Timestamp {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+// Deserialize from either a string, or a number of seconds
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Timestamp, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum TimestampJson {
+            Seconds(f64),
+            String(String),
+        }
+
+        match TimestampJson::deserialize(deserializer)? {
+            TimestampJson::Seconds(secs) => Duration::try_from_secs_f64(secs)
+                .map(Timestamp)
+                .map_err(serde::de::Error::custom),
+            TimestampJson::String(s) => s.parse().map_err(|err| {
+                serde::de::Error::custom(format!("invalid timestamp {s:?}: {err:?}"))
+            }),
+        }
+    }
+}
@@ -6,8 +6,11 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
+use super::credentials::{AwsEnvError, S3Credentials};
 use super::Format;
 
+pub use super::selection::{PageSelection, PageSelectionError, Timestamp, TimestampError};
+
 /// The input of a task: either a single task, or a list of multiple tasks.
 ///
 /// This implements `From<T>` for most sensible `T`.
@@ -51,6 +54,25 @@ impl<'a, 'b> Serialize for Input<'a, 'b> {
     }
 }
 
+// Deserialize from either a single task name, or a list of task names
+impl<'de, 'a, 'b> Deserialize<'de> for Input<'a, 'b> {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Input<'a, 'b>, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum InputJson {
+            Single(String),
+            List(Vec<String>),
+        }
+
+        Ok(match InputJson::deserialize(deserializer)? {
+            InputJson::Single(item) => item.into(),
+            InputJson::List(items) => items.into(),
+        })
+    }
+}
+
 impl<'a, 'b> From<&'a str> for Input<'a, 'b> {
     fn from(s: &'a str) -> Input<'a, 'b> {
         Input::Single(Cow::Borrowed(s))
@@ -107,6 +129,73 @@ impl<'a, 'b> From<&'b [Cow<'a, str>]> for Input<'a, 'b> {
     }
 }
 
+/// A secret value, such as a password, private key or access key.
+///
+/// This serializes to the real value, so it's sent to the API, but its [`Debug`] and
+/// [`std::fmt::Display`] implementations print `[REDACTED]`, so logging a task (or a
+/// [`crate::job::Create`]) doesn't leak credentials. Use [`Secret::expose`] to read the value.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Secret<'a>(Cow<'a, str>);
+
+impl<'a> Secret<'a> {
+    pub fn new(secret: impl Into<Cow<'a, str>>) -> Secret<'a> {
+        Secret(secret.into())
+    }
+
+    /// Returns the secret value.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    /// Convert `Secret<'a>` into `Secret<'static>`.
+    pub fn into_static(self) -> Secret<'static> {
+        Secret(Cow::Owned(self.0.into_owned()))
+    }
+}
+
+impl<'a> std::fmt::Debug for Secret<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Secret([REDACTED])")
+    }
+}
+
+impl<'a> std::fmt::Display for Secret<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("[REDACTED]")
+    }
+}
+
+// Serialize to the real value
+impl<'a> Serialize for Secret<'a> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de, 'a> Deserialize<'de> for Secret<'a> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Secret<'a>, D::Error> {
+        String::deserialize(deserializer).map(Secret::from)
+    }
+}
+
+impl<'a> From<Cow<'a, str>> for Secret<'a> {
+    fn from(secret: Cow<'a, str>) -> Secret<'a> {
+        Secret(secret)
+    }
+}
+
+impl<'a> From<&'a str> for Secret<'a> {
+    fn from(secret: &'a str) -> Secret<'a> {
+        Secret(Cow::Borrowed(secret))
+    }
+}
+
+impl<'a> From<String> for Secret<'a> {
+    fn from(secret: String) -> Secret<'a> {
+        Secret(Cow::Owned(secret))
+    }
+}
+
 macro_rules! make_task_types {
     //(__field_type opt: str) => {
     //    make_task_types!(__field_type opt $field_name: Cow<'a str>);
@@ -152,6 +241,40 @@ macro_rules! make_task_types {
             )*
         }
 
+        // Serialize to an object with an `"operation"` field, as used in a job call.
+        impl<'a> Serialize for $Task<'a> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                self.to_job_task()
+                    .map_err(serde::ser::Error::custom)?
+                    .serialize(serializer)
+            }
+        }
+
+        // Deserialize from an object with an `"operation"` field, as used in a job call.
+        impl<'de, 'a> Deserialize<'de> for $Task<'a> {
+            fn deserialize<D: serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<$Task<'a>, D::Error> {
+                use serde::de::Error;
+
+                let mut task = serde_json::Map::<String, serde_json::Value>::deserialize(deserializer)?;
+                let operation = match task.remove("operation") {
+                    Some(serde_json::Value::String(operation)) => operation,
+                    Some(_) => return Err(D::Error::custom("the operation must be a string")),
+                    None => return Err(D::Error::missing_field("operation")),
+                };
+                let task = serde_json::Value::Object(task);
+                match operation.as_str() {
+                    $(
+                        $operation => $TaskName::deserialize(task)
+                            .map($Task::$TaskName)
+                            .map_err(|err| D::Error::custom(format!("invalid {operation} task: {err}"))),
+                    )*
+                    _ => Err(D::Error::unknown_variant(&operation, &[$($operation),*])),
+                }
+            }
+        }
+
         impl<'a> $Task<'a> {
             /// Returns the operation name of this task. For example `convert` or `import/url`.
             pub fn operation(&self) -> &'static str {
@@ -173,7 +296,7 @@ macro_rules! make_task_types {
 
         $(
             $(#[$task_meta])*
-            #[derive(serde::Serialize, Debug)]
+            #[derive(serde::Serialize, serde::Deserialize, Debug)]
             $struct_vis struct $TaskName<'a> {
                 $(
                     $(#[$req_field_meta])*
@@ -184,6 +307,11 @@ macro_rules! make_task_types {
                     #[serde(skip_serializing_if = "Option::is_none")]
                     $struct_vis $opt_field_name: make_task_types!(__field_type opt: $opt_field_type),
                 )*
+                /// Any other options, which are passed to CloudConvert unchanged.
+                ///
+                /// This can be used to set options which aren't supported by this crate yet.
+                #[serde(flatten)]
+                $struct_vis extra: serde_json::Map<String, serde_json::Value>,
             }
 
             //$(
@@ -194,7 +322,40 @@ macro_rules! make_task_types {
             //    }
             //)?
 
-            hapic::json_api_call!(json <'a> ($crate::ApiCall) $operation: $TaskName<'a> as $TaskName<'a> => TasksOutput as Status);
+            // This isn't implemented using `hapic::json_api_call!`, since that joins the endpoint and
+            // the operation without a `/`.
+            impl<'a> $crate::ApiCall for $TaskName<'a> {}
+
+            impl<'a> hapic::JsonApiCall for $TaskName<'a> {
+                type Output = Status;
+                type JsonResponse = TasksOutput;
+                type JsonRequest = $TaskName<'a>;
+
+                fn uri(&self, endpoint: &str) -> String {
+                    format!("{endpoint}/{}", $operation)
+                }
+
+                fn try_into_request(self) -> Result<$TaskName<'a>, hapic::Error> {
+                    Ok(self)
+                }
+
+                fn parse_json_response(
+                    status: hapic::http::StatusCode,
+                    content_type: Option<hapic::http::HeaderValue>,
+                    raw_resp: Vec<u8>,
+                    resp: serde_json::Result<TasksOutput>,
+                ) -> Result<Status, hapic::Error> {
+                    if status.is_success() {
+                        Ok(resp?.into())
+                    } else {
+                        Err(hapic::Error::HttpStatusNotSuccess {
+                            status,
+                            content_type,
+                            body: raw_resp,
+                        })
+                    }
+                }
+            }
 
             impl<'a> From<$TaskName<'a>> for $Task<'a> {
                 fn from(task: $TaskName<'a>) -> $Task<'a> {
@@ -238,6 +399,7 @@ impl<'a> Task<'a> {
 
 make_task_types!(
     /// A task. See the [crate level docs](crate) for a nice list of available tasks.
+    #[derive(Debug)]
     pub enum Task<'a>;
 
     /// Import a file by downloading it from a URL.
@@ -251,8 +413,23 @@ make_task_types!(
         opt headers: HashMap<String, String>,
     }
 
+    /// Create a task which a file can be uploaded to.
+    ///
+    /// Once the task has been created, [`Status::upload_form`] returns the form which the file
+    /// should be uploaded to.
+    ///
+    /// Docs: [api/v2/import#import-upload-tasks](https://cloudconvert.com/api/v2/import#import-upload-tasks)
+    pub struct ImportUpload<'a> {
+        operation: "import/upload",
+
+        opt redirect: Cow<'a, str>,
+    }
+
     /// Import a document from an S3 compatible bucket.
     ///
+    /// The credentials can be read from the standard `AWS_*` environment variables using
+    /// [`ImportS3::from_env`].
+    ///
     /// Docs: [api/v2/import#import-s3-tasks](https://cloudconvert.com/api/v2/import#import-s3-tasks)
     pub struct ImportS3<'a> {
         operation: "import/s3",
@@ -260,11 +437,11 @@ make_task_types!(
         req bucket: Cow<'a, str>,
         req region: Cow<'a, str>,
         req access_key_id: Cow<'a, str>,
-        req secret_access_key: Cow<'a, str>,
+        req secret_access_key: Secret<'a>,
         opt endpoint: Cow<'a, str>,
         opt key: Cow<'a, str>,
         opt key_prefix: Cow<'a, str>,
-        opt session_token: Cow<'a, str>,
+        opt session_token: Secret<'a>,
         opt filename: Cow<'a, str>,
     }
 
@@ -276,8 +453,8 @@ make_task_types!(
 
         req storage_account: Cow<'a, str>,
         req container: Cow<'a, str>,
-        opt storage_access_key: Cow<'a, str>,
-        opt sas_token: Cow<'a, str>,
+        opt storage_access_key: Secret<'a>,
+        opt sas_token: Secret<'a>,
         opt blob: Cow<'a, str>,
         opt blob_prefix: Cow<'a, str>,
         opt filename: Cow<'a, str>,
@@ -292,7 +469,7 @@ make_task_types!(
         req project_id: Cow<'a, str>,
         req bucket: Cow<'a, str>,
         req client_email: Cow<'a, str>,
-        req private_key: Cow<'a, str>,
+        req private_key: Secret<'a>,
         opt file: Cow<'a, str>,
         opt file_prefix: Cow<'a, str>,
         opt filename: Cow<'a, str>,
@@ -306,7 +483,7 @@ make_task_types!(
 
         req auth_url: Cow<'a, str>,
         req username: Cow<'a, str>,
-        req password: Cow<'a, str>,
+        req password: Secret<'a>,
         req region: Cow<'a, str>,
         req container: Cow<'a, str>,
         opt tenant_name: Cow<'a, str>,
@@ -323,9 +500,9 @@ make_task_types!(
 
         req host: Cow<'a, str>,
         req username: Cow<'a, str>,
-        req password: Cow<'a, str>,
         opt port: u16,
-        opt private_key: Cow<'a, str>,
+        opt password: Secret<'a>,
+        opt private_key: Secret<'a>,
         opt file: Cow<'a, str>,
         opt path: Cow<'a, str>,
         opt filename: Cow<'a, str>,
@@ -345,6 +522,12 @@ make_task_types!(
         /// This is most likely an import task.
         req input: Input<'a, 'a>,
         req output_format: Format,
+        /// Options specific to the input or output format.
+        ///
+        /// Other options can be set using the `extra` field. The options supported by each
+        /// conversion can be listed using [`crate::conversion::ConvertFormats`].
+        #[serde(flatten, deserialize_with = "deserialize_convert_options")]
+        req options: Option<ConvertOptions>,
         opt input_format: Format,
         opt filename: Cow<'a, str>,
         opt engine: Cow<'a, str>,
@@ -374,6 +557,9 @@ make_task_types!(
 
     /// Add a watermark to a PDF, image, or video.
     ///
+    /// The watermark is either `text`, or an `image`. The `image` can be the name of another task
+    /// in the job (such as an import task) which provides the image, or a URL.
+    ///
     /// Docs: [api/v2/watermark](https://cloudconvert.com/api/v2/watermark)
     pub struct Watermark<'a> {
         operation: "watermark",
@@ -383,21 +569,22 @@ make_task_types!(
         /// This is most likely an import task.
         req input: Input<'a, 'a>,
         opt input_format: Format,
-        opt pages: Cow<'a, str>,
+        opt pages: PageSelection,
         opt layer: WatermarkLayer,
         opt text: Cow<'a, str>,
         opt font_size: u16,
-        opt font_width_percent: u8,
-        opt font_color: Cow<'a, str>,
-        opt font_name: Cow<'a, str>,
+        opt font_width_percent: Percentage,
+        opt font_color: Rgb,
+        opt font_name: WatermarkFont<'a>,
         opt font_align: FontAlign,
-        opt image: Cow<'a, str>,
+        opt image: Input<'a, 'a>,
         opt image_width: u32,
-        opt image_width_percent: u8,
+        opt image_width_percent: Percentage,
         opt position_vertical: VerticalPosition,
         opt position_horizontal: HorizontalPosition,
         opt margin_vertical: u32,
-        opt opacity: u8,
+        opt margin_horizontal: u32,
+        opt opacity: Percentage,
         opt rotation: u16,
         opt filename: Cow<'a, str>,
         opt engine: Cow<'a, str>,
@@ -422,7 +609,7 @@ make_task_types!(
         opt height: u32,
         opt fit: ThumbnailFit,
         opt count: u32,
-        opt timestamp: Cow<'a, str>,
+        opt timestamp: Timestamp,
         opt filename: Cow<'a, str>,
         opt engine: Cow<'a, str>,
         opt engine_version: Cow<'a, str>,
@@ -475,24 +662,196 @@ make_task_types!(
         req url: Cow<'a, str>,
         /// This should be `pdf`, `png` or `jpg`.
         req output_format: Format,
+        #[serde(default)]
         req print_background: bool,
+        #[serde(default)]
         req display_header_footer: bool,
-        #[serde(skip_serializing_if = "HashMap::is_empty")]
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
         req headers: HashMap<String, String>,
-        opt pages: Cow<'a, str>,
+        opt pages: PageSelection,
         opt zoom: f32,
         opt page_width: f32,
         opt page_height: f32,
         opt margin_top: f32,
         opt margin_bottom: f32,
         opt margin_left: f32,
+        opt margin_right: f32,
         opt header_template: Cow<'a, str>,
         opt footer_template: Cow<'a, str>,
         opt wait_until: CaptureWaitUntil,
         opt wait_for_element: Cow<'a, str>,
         opt wait_time: u32,
         opt css_media_type: CssMediaType,
-        opt filename: HashMap<String, String>,
+        opt filename: Cow<'a, str>,
+        opt engine: Cow<'a, str>,
+        opt engine_version: Cow<'a, str>,
+        opt timeout: u32,
+    }
+
+    /// Read the metadata of a file.
+    ///
+    /// The metadata is available in the `metadata` field of the task result.
+    ///
+    /// Docs: [api/v2/metadata](https://cloudconvert.com/api/v2/metadata)
+    pub struct Metadata<'a> {
+        operation: "metadata",
+
+        /// The ID of the task to read the metadata of.
+        ///
+        /// This is most likely an import task.
+        req input: Input<'a, 'a>,
+        opt input_format: Format,
+        opt engine: Cow<'a, str>,
+        opt engine_version: Cow<'a, str>,
+        opt timeout: u32,
+    }
+
+    /// Write metadata to a file.
+    ///
+    /// Docs: [api/v2/metadata#metadata-write-tasks](https://cloudconvert.com/api/v2/metadata#metadata-write-tasks)
+    pub struct WriteMetadata<'a> {
+        operation: "metadata/write",
+
+        /// The ID of the task to write the metadata to.
+        ///
+        /// This is most likely an import task.
+        req input: Input<'a, 'a>,
+        /// The metadata to write, for example `Author` or `Title`.
+        req metadata: HashMap<String, String>,
+        opt input_format: Format,
+        opt filename: Cow<'a, str>,
+        opt engine: Cow<'a, str>,
+        opt engine_version: Cow<'a, str>,
+        opt timeout: u32,
+    }
+
+    /// Convert a PDF to PDF/A.
+    ///
+    /// Docs: [api/v2/pdf#pdf-a-tasks](https://cloudconvert.com/api/v2/pdf#pdf-a-tasks)
+    pub struct PdfA<'a> {
+        operation: "pdf/a",
+
+        /// The ID of the task, or tasks, to convert.
+        ///
+        /// This is most likely an import task.
+        req input: Input<'a, 'a>,
+        opt conformance_level: PdfAConformanceLevel,
+        opt filename: Cow<'a, str>,
+        opt engine: Cow<'a, str>,
+        opt engine_version: Cow<'a, str>,
+        opt timeout: u32,
+    }
+
+    /// Add a text layer to a scanned PDF using optical character recognition.
+    ///
+    /// The `language` field is a list of the languages in the document, as ISO 639-2 codes, for
+    /// example `eng` or `deu`.
+    ///
+    /// Docs: [api/v2/pdf#pdf-ocr-tasks](https://cloudconvert.com/api/v2/pdf#pdf-ocr-tasks)
+    pub struct PdfOcr<'a> {
+        operation: "pdf/ocr",
+
+        /// The ID of the task, or tasks, to OCR.
+        ///
+        /// This is most likely an import task.
+        req input: Input<'a, 'a>,
+        opt auto_orient: bool,
+        opt language: Vec<Cow<'a, str>>,
+        opt filename: Cow<'a, str>,
+        opt engine: Cow<'a, str>,
+        opt engine_version: Cow<'a, str>,
+        opt timeout: u32,
+    }
+
+    /// Split a PDF into one PDF per page.
+    ///
+    /// Docs: [api/v2/pdf#pdf-split-pages-tasks](https://cloudconvert.com/api/v2/pdf#pdf-split-pages-tasks)
+    pub struct PdfSplitPages<'a> {
+        operation: "pdf/split-pages",
+
+        /// The ID of the task, or tasks, to split.
+        ///
+        /// This is most likely an import task.
+        req input: Input<'a, 'a>,
+        opt engine: Cow<'a, str>,
+        opt engine_version: Cow<'a, str>,
+        opt timeout: u32,
+    }
+
+    /// Extract pages from a PDF into a new PDF.
+    ///
+    /// Docs: [api/v2/pdf#pdf-extract-pages-tasks](https://cloudconvert.com/api/v2/pdf#pdf-extract-pages-tasks)
+    pub struct PdfExtractPages<'a> {
+        operation: "pdf/extract-pages",
+
+        /// The ID of the task, or tasks, to extract pages from.
+        ///
+        /// This is most likely an import task.
+        req input: Input<'a, 'a>,
+        /// The pages to extract.
+        req pages: PageSelection,
+        opt filename: Cow<'a, str>,
+        opt engine: Cow<'a, str>,
+        opt engine_version: Cow<'a, str>,
+        opt timeout: u32,
+    }
+
+    /// Rotate pages of a PDF.
+    ///
+    /// If the `pages` field isn't set, all pages are rotated.
+    ///
+    /// Docs: [api/v2/pdf#pdf-rotate-pages-tasks](https://cloudconvert.com/api/v2/pdf#pdf-rotate-pages-tasks)
+    pub struct PdfRotatePages<'a> {
+        operation: "pdf/rotate-pages",
+
+        /// The ID of the task, or tasks, to rotate.
+        ///
+        /// This is most likely an import task.
+        req input: Input<'a, 'a>,
+        req rotation: PageRotation,
+        opt pages: PageSelection,
+        opt filename: Cow<'a, str>,
+        opt engine: Cow<'a, str>,
+        opt engine_version: Cow<'a, str>,
+        opt timeout: u32,
+    }
+
+    /// Encrypt a PDF, and set its permissions.
+    ///
+    /// Docs: [api/v2/pdf#pdf-encrypt-tasks](https://cloudconvert.com/api/v2/pdf#pdf-encrypt-tasks)
+    pub struct PdfEncrypt<'a> {
+        operation: "pdf/encrypt",
+
+        /// The ID of the task, or tasks, to encrypt.
+        ///
+        /// This is most likely an import task.
+        req input: Input<'a, 'a>,
+        opt input_format: Format,
+        opt set_password: Secret<'a>,
+        opt set_owner_password: Secret<'a>,
+        opt allow_print: bool,
+        opt allow_modify: bool,
+        opt allow_copy: bool,
+        opt allow_annotate: bool,
+        opt filename: Cow<'a, str>,
+        opt engine: Cow<'a, str>,
+        opt engine_version: Cow<'a, str>,
+        opt timeout: u32,
+    }
+
+    /// Remove the password and restrictions from a PDF.
+    ///
+    /// Docs: [api/v2/pdf#pdf-decrypt-tasks](https://cloudconvert.com/api/v2/pdf#pdf-decrypt-tasks)
+    pub struct PdfDecrypt<'a> {
+        operation: "pdf/decrypt",
+
+        /// The ID of the task, or tasks, to decrypt.
+        ///
+        /// This is most likely an import task.
+        req input: Input<'a, 'a>,
+        req password: Secret<'a>,
+        opt input_format: Format,
+        opt filename: Cow<'a, str>,
         opt engine: Cow<'a, str>,
         opt engine_version: Cow<'a, str>,
         opt timeout: u32,
@@ -506,12 +865,17 @@ make_task_types!(
 
         /// The ID of the task, or tasks, to export.
         req input: Input<'a, 'a>,
+        #[serde(default)]
         req inline: bool,
+        #[serde(default)]
         req archive_multiple_files: bool,
     }
 
     /// Export files to an S3 compatible bucket.
     ///
+    /// The credentials can be read from the standard `AWS_*` environment variables using
+    /// [`ExportS3::from_env`].
+    ///
     /// Docs [api/v2/export#export-s3-tasks](https://cloudconvert.com/api/v2/export#export-s3-tasks)
     pub struct ExportS3<'a> {
         operation: "export/s3",
@@ -521,18 +885,18 @@ make_task_types!(
         req bucket: Cow<'a, str>,
         req region: Cow<'a, str>,
         req access_key_id: Cow<'a, str>,
-        req secret_access_key: Cow<'a, str>,
+        req secret_access_key: Secret<'a>,
         opt endpoint: Cow<'a, str>,
         opt key: Cow<'a, str>,
         opt key_prefix: Cow<'a, str>,
-        opt session_token: Cow<'a, str>,
-        opt acl: Cow<'a, str>,
+        opt session_token: Secret<'a>,
+        opt acl: S3Acl,
         opt cache_control: Cow<'a, str>,
         opt content_disposition: Cow<'a, str>,
         opt content_type: Cow<'a, str>,
-        opt metadata: serde_json::Value,
-        opt server_side_encrpytion: Cow<'a, str>,
-        opt tagging: serde_json::Value,
+        opt metadata: HashMap<String, String>,
+        opt server_side_encryption: ServerSideEncryption,
+        opt tagging: HashMap<String, String>,
     }
 
     /// Export files to Azure Blob Storage
@@ -545,8 +909,8 @@ make_task_types!(
         req input: Input<'a, 'a>,
         req storage_account: Cow<'a, str>,
         req container: Cow<'a, str>,
-        opt storage_access_key: Cow<'a, str>,
-        opt sas_token: Cow<'a, str>,
+        opt storage_access_key: Secret<'a>,
+        opt sas_token: Secret<'a>,
         opt blob: Cow<'a, str>,
         opt blob_prefix: Cow<'a, str>,
         opt metadata: serde_json::Value,
@@ -563,7 +927,7 @@ make_task_types!(
         req project_id: Cow<'a, str>,
         req bucket: Cow<'a, str>,
         req client_email: Cow<'a, str>,
-        req private_key: Cow<'a, str>,
+        req private_key: Secret<'a>,
         opt file: Cow<'a, str>,
         opt file_prefix: Cow<'a, str>,
     }
@@ -578,7 +942,7 @@ make_task_types!(
         req input: Input<'a, 'a>,
         req auth_url: Cow<'a, str>,
         req username: Cow<'a, str>,
-        req password: Cow<'a, str>,
+        req password: Secret<'a>,
         req region: Cow<'a, str>,
         req container: Cow<'a, str>,
         opt tenant_name: Cow<'a, str>,
@@ -597,17 +961,238 @@ make_task_types!(
         req host: Cow<'a, str>,
         req username: Cow<'a, str>,
         opt port: u16,
-        opt password: Cow<'a, str>,
-        opt private_key: Cow<'a, str>,
+        opt password: Secret<'a>,
+        opt private_key: Secret<'a>,
         opt file: Cow<'a, str>,
         opt path: Cow<'a, str>,
     }
 );
 
+/// An RGB color, used for the `font_color` property of [`Watermark`] tasks.
+///
+/// This serializes to a hex string, such as `#ff0080`, and can be parsed from one with or without
+/// the leading `#`, in either the long (`#ff0080`) or short (`#f08`) form.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+/// An error returned when parsing an [`Rgb`] color.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ColorParseError {
+    /// The color wasn't 3 or 6 hex digits long.
+    InvalidLength(usize),
+
+    /// The color contained a character which isn't a hex digit.
+    InvalidHexDigit,
+}
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb::new(0, 0, 0);
+    pub const WHITE: Rgb = Rgb::new(255, 255, 255);
+
+    pub const fn new(r: u8, g: u8, b: u8) -> Rgb {
+        Rgb { r, g, b }
+    }
+
+    /// Parse a hex color, such as `#ff0080`, `ff0080` or `#f08`.
+    pub fn from_hex(hex: &str) -> Result<Rgb, ColorParseError> {
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+        if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(ColorParseError::InvalidHexDigit);
+        }
+        let digit = |idx: usize| u8::from_str_radix(&hex[idx..idx + 1], 16).unwrap();
+        match hex.len() {
+            3 => Ok(Rgb::new(digit(0) * 17, digit(1) * 17, digit(2) * 17)),
+            6 => Ok(Rgb::new(
+                digit(0) * 16 + digit(1),
+                digit(2) * 16 + digit(3),
+                digit(4) * 16 + digit(5),
+            )),
+            len => Err(ColorParseError::InvalidLength(len)),
+        }
+    }
+}
+
+impl std::fmt::Display for Rgb {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+impl std::str::FromStr for Rgb {
+    type Err = ColorParseError;
+
+    fn from_str(s: &str) -> Result<Rgb, ColorParseError> {
+        Rgb::from_hex(s)
+    }
+}
+
+// Serialize to the hex string value
+impl Serialize for Rgb {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+// Deserialize, and validate, from the hex string value
+impl<'de> Deserialize<'de> for Rgb {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Rgb, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse()
+            .map_err(|err| serde::de::Error::custom(format!("invalid color {s:?}: {err:?}")))
+    }
+}
+
+/// A percentage, from 0 to 100 inclusive.
+///
+/// Used for the `opacity`, `font_width_percent` and `image_width_percent` properties of
+/// [`Watermark`] tasks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Percentage(u8);
+
+/// An error returned when creating a [`Percentage`] greater than 100.
+#[derive(Debug, PartialEq, Eq)]
+pub struct PercentageOutOfRange(pub u8);
+
+impl Percentage {
+    pub const ZERO: Percentage = Percentage(0);
+    pub const FULL: Percentage = Percentage(100);
+
+    /// Create a percentage, returning an error if `percent` is greater than 100.
+    pub const fn new(percent: u8) -> Result<Percentage, PercentageOutOfRange> {
+        if percent > 100 {
+            Err(PercentageOutOfRange(percent))
+        } else {
+            Ok(Percentage(percent))
+        }
+    }
+
+    pub const fn get(self) -> u8 {
+        self.0
+    }
+}
+
+impl TryFrom<u8> for Percentage {
+    type Error = PercentageOutOfRange;
+
+    fn try_from(percent: u8) -> Result<Percentage, PercentageOutOfRange> {
+        Percentage::new(percent)
+    }
+}
+
+// Serialize to the number
+impl Serialize for Percentage {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.0)
+    }
+}
+
+// Deserialize, and validate, from the number
+impl<'de> Deserialize<'de> for Percentage {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Percentage, D::Error> {
+        let percent = u8::deserialize(deserializer)?;
+        Percentage::new(percent).map_err(|_| {
+            serde::de::Error::custom(format!("percentage {percent} is greater than 100"))
+        })
+    }
+}
+
+/// Enum for the `font_name` property of [`Watermark`] tasks.
+///
+/// Any font not listed here can be used with [`WatermarkFont::Custom`].
+///
+/// Docs: [api/v2/watermark](https://cloudconvert.com/api/v2/watermark)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WatermarkFont<'a> {
+    AndaleMono,
+    Arial,
+    ArialBlack,
+    ComicSansMs,
+    CourierNew,
+    Georgia,
+    Helvetica,
+    Impact,
+    TimesNewRoman,
+    TrebuchetMs,
+    Verdana,
+    Custom(Cow<'a, str>),
+}
+
+impl<'a> WatermarkFont<'a> {
+    /// Returns the name of the font, as used in the CloudConvert API.
+    pub fn name(&self) -> &str {
+        match self {
+            WatermarkFont::AndaleMono => "Andale Mono",
+            WatermarkFont::Arial => "Arial",
+            WatermarkFont::ArialBlack => "Arial Black",
+            WatermarkFont::ComicSansMs => "Comic Sans MS",
+            WatermarkFont::CourierNew => "Courier New",
+            WatermarkFont::Georgia => "Georgia",
+            WatermarkFont::Helvetica => "Helvetica",
+            WatermarkFont::Impact => "Impact",
+            WatermarkFont::TimesNewRoman => "Times New Roman",
+            WatermarkFont::TrebuchetMs => "Trebuchet MS",
+            WatermarkFont::Verdana => "Verdana",
+            WatermarkFont::Custom(name) => name,
+        }
+    }
+}
+
+impl<'a> From<Cow<'a, str>> for WatermarkFont<'a> {
+    fn from(name: Cow<'a, str>) -> WatermarkFont<'a> {
+        [
+            WatermarkFont::AndaleMono,
+            WatermarkFont::Arial,
+            WatermarkFont::ArialBlack,
+            WatermarkFont::ComicSansMs,
+            WatermarkFont::CourierNew,
+            WatermarkFont::Georgia,
+            WatermarkFont::Helvetica,
+            WatermarkFont::Impact,
+            WatermarkFont::TimesNewRoman,
+            WatermarkFont::TrebuchetMs,
+            WatermarkFont::Verdana,
+        ]
+        .into_iter()
+        .find(|font| font.name() == name)
+        .unwrap_or(WatermarkFont::Custom(name))
+    }
+}
+
+impl<'a> From<&'a str> for WatermarkFont<'a> {
+    fn from(name: &'a str) -> WatermarkFont<'a> {
+        WatermarkFont::from(Cow::Borrowed(name))
+    }
+}
+
+impl<'a> From<String> for WatermarkFont<'a> {
+    fn from(name: String) -> WatermarkFont<'a> {
+        WatermarkFont::from(Cow::Owned(name))
+    }
+}
+
+// Serialize to the font name
+impl<'a> Serialize for WatermarkFont<'a> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de, 'a> Deserialize<'de> for WatermarkFont<'a> {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<WatermarkFont<'a>, D::Error> {
+        String::deserialize(deserializer).map(WatermarkFont::from)
+    }
+}
+
 /// Enum for the `font_align` property of [`Watermark`] tasks.
 ///
 /// Docs: [api/v2/watermark](https://cloudconvert.com/api/v2/watermark)
-#[derive(Debug, Serialize, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum FontAlign {
     #[serde(rename = "left")]
     Left,
@@ -620,7 +1205,7 @@ pub enum FontAlign {
 /// Enum for the `layer` property of [`Watermark`] tasks.
 ///
 /// Docs: [api/v2/watermark](https://cloudconvert.com/api/v2/watermark)
-#[derive(Debug, Serialize, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum WatermarkLayer {
     #[serde(rename = "above")]
     Above,
@@ -631,12 +1216,12 @@ pub enum WatermarkLayer {
 /// Enum for the `position_vertical` property of [`Watermark`] tasks.
 ///
 /// Docs: [api/v2/watermark](https://cloudconvert.com/api/v2/watermark)
-#[derive(Debug, Serialize, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum VerticalPosition {
-    #[serde(rename = "left")]
-    Left,
-    #[serde(rename = "right")]
-    Right,
+    #[serde(rename = "top")]
+    Top,
+    #[serde(rename = "bottom")]
+    Bottom,
     #[serde(rename = "center")]
     Center,
 }
@@ -644,12 +1229,12 @@ pub enum VerticalPosition {
 /// Enum for the `position_horizontal` property of [`Watermark`] tasks.
 ///
 /// Docs: [api/v2/watermark](https://cloudconvert.com/api/v2/watermark)
-#[derive(Debug, Serialize, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum HorizontalPosition {
-    #[serde(rename = "top")]
-    Top,
-    #[serde(rename = "bottom")]
-    Bottom,
+    #[serde(rename = "left")]
+    Left,
+    #[serde(rename = "right")]
+    Right,
     #[serde(rename = "center")]
     Center,
 }
@@ -657,7 +1242,7 @@ pub enum HorizontalPosition {
 /// Enum for the `fit` property of [`Thumbnail`] tasks.
 ///
 /// Docs: [api/v2/thumbnail](https://cloudconvert.com/api/v2/thumbnail)
-#[derive(Debug, Serialize, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum ThumbnailFit {
     #[serde(rename = "max")]
     Max,
@@ -670,7 +1255,7 @@ pub enum ThumbnailFit {
 /// Enum for the `profile` property of [`Optimize`] tasks.
 ///
 /// Docs: [api/v2/optimize](https://cloudconvert.com/api/v2/optimize)
-#[derive(Debug, Serialize, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum OptimizationProfile {
     #[serde(rename = "web")]
     Web,
@@ -687,7 +1272,7 @@ pub enum OptimizationProfile {
 /// Enum for the `colorspace` property of [`Optimize`] tasks.
 ///
 /// Docs: [api/v2/optimize](https://cloudconvert.com/api/v2/optimize)
-#[derive(Debug, Serialize, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum Colorspace {
     #[serde(rename = "unchanged")]
     Unchanged,
@@ -699,12 +1284,66 @@ pub enum Colorspace {
     Greyscale,
 }
 
+/// Enum for the `acl` property of [`ExportS3`] tasks.
+///
+/// Docs: [api/v2/export#export-s3-tasks](https://cloudconvert.com/api/v2/export#export-s3-tasks)
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum S3Acl {
+    #[serde(rename = "private")]
+    Private,
+    #[serde(rename = "public-read")]
+    PublicRead,
+    #[serde(rename = "public-read-write")]
+    PublicReadWrite,
+    #[serde(rename = "authenticated-read")]
+    AuthenticatedRead,
+    #[serde(rename = "bucket-owner-read")]
+    BucketOwnerRead,
+    #[serde(rename = "bucket-owner-full-control")]
+    BucketOwnerFullControl,
+}
+
+/// Enum for the `server_side_encryption` property of [`ExportS3`] tasks.
+///
+/// Docs: [api/v2/export#export-s3-tasks](https://cloudconvert.com/api/v2/export#export-s3-tasks)
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ServerSideEncryption {
+    #[serde(rename = "AES256")]
+    Aes256,
+    #[serde(rename = "aws:kms")]
+    AwsKms,
+}
+
+impl<'a> ImportS3<'a> {
+    /// Create an import task for `bucket`, with the region, credentials and endpoint read from
+    /// the standard `AWS_*` environment variables.
+    ///
+    /// Either [`ImportS3::key`] or [`ImportS3::key_prefix`] should be set before using the task.
+    /// See [`S3Credentials::from_env`] to create several tasks from the same credentials.
+    pub fn from_env(bucket: impl Into<Cow<'a, str>>) -> Result<ImportS3<'a>, AwsEnvError> {
+        Ok(S3Credentials::from_env(bucket)?.import_task(None, None))
+    }
+}
+
+impl<'a> ExportS3<'a> {
+    /// Create an export task, exporting `input` to `bucket`, with the region, credentials and
+    /// endpoint read from the standard `AWS_*` environment variables.
+    ///
+    /// See [`S3Credentials::from_env`] to create several tasks from the same credentials.
+    pub fn from_env(
+        input: impl Into<Input<'a, 'a>>,
+        bucket: impl Into<Cow<'a, str>>,
+    ) -> Result<ExportS3<'a>, AwsEnvError> {
+        Ok(S3Credentials::from_env(bucket)?.export_task(input.into(), None, None))
+    }
+}
+
 /// Enum for the `wait_until` property of [`Capture`] tasks.
 ///
 /// Docs: [api/v2/capture-website](https://cloudconvert.com/api/v2/capture-website)
-#[derive(Debug, Serialize, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum CaptureWaitUntil {
-    #[serde(rename = "Load")]
+    #[serde(rename = "load")]
     Load,
     #[serde(rename = "domcontentloaded")]
     DOMContentLoaded,
@@ -714,10 +1353,10 @@ pub enum CaptureWaitUntil {
     NetworkIdle2,
 }
 
-/// Enum for the `css_media_type` property of [`CssMediaType`] tasks.
+/// Enum for the `css_media_type` property of [`Capture`] tasks.
 ///
 /// Docs: [api/v2/capture-website](https://cloudconvert.com/api/v2/capture-website)
-#[derive(Debug, Serialize, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum CssMediaType {
     #[serde(rename = "print")]
     Print,
@@ -725,6 +1364,376 @@ pub enum CssMediaType {
     Screen,
 }
 
+/// Enum for the `conformance_level` property of [`PdfA`] tasks.
+///
+/// Docs: [api/v2/pdf#pdf-a-tasks](https://cloudconvert.com/api/v2/pdf#pdf-a-tasks)
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum PdfAConformanceLevel {
+    #[serde(rename = "1a")]
+    Level1A,
+    #[serde(rename = "1b")]
+    Level1B,
+    #[serde(rename = "2a")]
+    Level2A,
+    #[serde(rename = "2b")]
+    Level2B,
+    #[serde(rename = "2u")]
+    Level2U,
+    #[serde(rename = "3a")]
+    Level3A,
+    #[serde(rename = "3b")]
+    Level3B,
+    #[serde(rename = "3u")]
+    Level3U,
+}
+
+/// Enum for the `rotation` property of [`PdfRotatePages`] tasks. All rotations are clockwise.
+///
+/// Docs: [api/v2/pdf#pdf-rotate-pages-tasks](https://cloudconvert.com/api/v2/pdf#pdf-rotate-pages-tasks)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PageRotation {
+    Rotate90,
+    Rotate180,
+    Rotate270,
+}
+
+impl PageRotation {
+    /// Returns the clockwise rotation in degrees.
+    pub fn degrees(self) -> u16 {
+        match self {
+            PageRotation::Rotate90 => 90,
+            PageRotation::Rotate180 => 180,
+            PageRotation::Rotate270 => 270,
+        }
+    }
+}
+
+// Serialize to the number of degrees
+impl Serialize for PageRotation {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u16(self.degrees())
+    }
+}
+
+// Deserialize from the number of degrees
+impl<'de> Deserialize<'de> for PageRotation {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<PageRotation, D::Error> {
+        match u16::deserialize(deserializer)? {
+            90 => Ok(PageRotation::Rotate90),
+            180 => Ok(PageRotation::Rotate180),
+            270 => Ok(PageRotation::Rotate270),
+            degrees => Err(serde::de::Error::custom(format!(
+                "invalid rotation {degrees}, expected 90, 180 or 270"
+            ))),
+        }
+    }
+}
+
+/// Options specific to the input or output format of a [`Convert`] task.
+///
+/// Docs: [api/v2/convert](https://cloudconvert.com/api/v2/convert)
+///
+/// The options are grouped by the kind of input or output they apply to, and any number of groups
+/// can be set together. Options which apply to several kinds, such as the output dimensions, are
+/// fields of this struct.
+///
+/// Only the most common options are included here. Others can be set using [`Convert::extra`].
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct ConvertOptions {
+    /// The width of the output, in pixels.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    /// The height of the output, in pixels.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    /// How the output should be resized to fit `width` and `height`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fit: Option<ImageFit>,
+    /// The pages to convert, for PDF and office document inputs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pages: Option<PageSelection>,
+    #[serde(flatten)]
+    pub image: ImageOptions,
+    #[serde(flatten)]
+    pub video: VideoOptions,
+    #[serde(flatten)]
+    pub pdf: PdfOptions,
+    #[serde(flatten)]
+    pub document: DocumentOptions,
+}
+
+impl From<ImageOptions> for ConvertOptions {
+    fn from(image: ImageOptions) -> ConvertOptions {
+        ConvertOptions {
+            image,
+            ..Default::default()
+        }
+    }
+}
+
+impl From<VideoOptions> for ConvertOptions {
+    fn from(video: VideoOptions) -> ConvertOptions {
+        ConvertOptions {
+            video,
+            ..Default::default()
+        }
+    }
+}
+
+impl From<PdfOptions> for ConvertOptions {
+    fn from(pdf: PdfOptions) -> ConvertOptions {
+        ConvertOptions {
+            pdf,
+            ..Default::default()
+        }
+    }
+}
+
+impl From<DocumentOptions> for ConvertOptions {
+    fn from(document: DocumentOptions) -> ConvertOptions {
+        ConvertOptions {
+            document,
+            ..Default::default()
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ConvertOptions {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        // The fields are listed, rather than the groups being flattened, so that when the options
+        // are flattened into a task, they're taken from the fields of the task, and don't also end
+        // up in `Convert::extra`. This must list every field of the options and their groups.
+        const FIELDS: &[&str] = &[
+            "width",
+            "height",
+            "fit",
+            "pages",
+            "strip",
+            "quality",
+            "auto_orient",
+            "video_codec",
+            "crf",
+            "preset",
+            "fps",
+            "audio_codec",
+            "audio_bitrate",
+            "audio_frequency",
+            "trim_start",
+            "trim_end",
+            "pixel_density",
+            "pdf_a",
+            "hidden_slides",
+        ];
+
+        struct FieldsVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for FieldsVisitor {
+            type Value = serde_json::Value;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("convert options")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<serde_json::Value, A::Error> {
+                let mut fields = serde_json::Map::new();
+                while let Some((name, value)) = map.next_entry::<String, serde_json::Value>()? {
+                    if FIELDS.contains(&name.as_str()) {
+                        fields.insert(name, value);
+                    }
+                }
+                Ok(serde_json::Value::Object(fields))
+            }
+        }
+
+        #[derive(Deserialize)]
+        struct Shared {
+            #[serde(default)]
+            width: Option<u32>,
+            #[serde(default)]
+            height: Option<u32>,
+            #[serde(default)]
+            fit: Option<ImageFit>,
+            #[serde(default)]
+            pages: Option<PageSelection>,
+        }
+
+        let fields = deserializer.deserialize_struct("ConvertOptions", FIELDS, FieldsVisitor)?;
+        let shared = Shared::deserialize(&fields).map_err(D::Error::custom)?;
+        Ok(ConvertOptions {
+            width: shared.width,
+            height: shared.height,
+            fit: shared.fit,
+            pages: shared.pages,
+            image: ImageOptions::deserialize(&fields).map_err(D::Error::custom)?,
+            video: VideoOptions::deserialize(&fields).map_err(D::Error::custom)?,
+            pdf: PdfOptions::deserialize(&fields).map_err(D::Error::custom)?,
+            document: DocumentOptions::deserialize(&fields).map_err(D::Error::custom)?,
+        })
+    }
+}
+
+/// Deserialize the [`Convert::options`] field, which is `None` if none of the options are set.
+fn deserialize_convert_options<'de, D>(deserializer: D) -> Result<Option<ConvertOptions>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let options = ConvertOptions::deserialize(deserializer)?;
+    Ok(Some(options).filter(|options| *options != ConvertOptions::default()))
+}
+
+/// Options for [`Convert`] tasks which output an image. See also [`ConvertOptions`].
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ImageOptions {
+    /// Remove any metadata, such as EXIF data, from the image.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strip: Option<bool>,
+    /// The compression quality of the image, from 1 to 100.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quality: Option<u8>,
+    /// Rotate the image according to its EXIF orientation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_orient: Option<bool>,
+}
+
+/// Options for [`Convert`] tasks which output a video or audio file. See also
+/// [`ConvertOptions`].
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct VideoOptions {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub video_codec: Option<VideoCodec>,
+    /// The constant rate factor, from 0 (lossless) to 63. Lower values give higher quality.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crf: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset: Option<VideoPreset>,
+    /// The frame rate of the output video.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fps: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio_codec: Option<AudioCodec>,
+    /// The audio bitrate, in kbit/s.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio_bitrate: Option<u32>,
+    /// The audio sample rate, in Hz.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio_frequency: Option<u32>,
+    /// Trim the input, starting at this timestamp.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trim_start: Option<Timestamp>,
+    /// Trim the input, ending at this timestamp.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trim_end: Option<Timestamp>,
+}
+
+/// Options for [`Convert`] tasks which take a PDF, or another vector format, as input. See also
+/// [`ConvertOptions`].
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PdfOptions {
+    /// The pixel density (DPI) used when rasterizing the input.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pixel_density: Option<u32>,
+}
+
+/// Options for [`Convert`] tasks which take an office document (such as `docx`, `xlsx` or `pptx`)
+/// as input. See also [`ConvertOptions`].
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DocumentOptions {
+    /// Create a PDF/A compliant output, when converting to PDF.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pdf_a: Option<bool>,
+    /// Include hidden slides, when converting presentations.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hidden_slides: Option<bool>,
+}
+
+/// Enum for the `fit` property of [`ConvertOptions`].
+///
+/// Docs: [api/v2/convert](https://cloudconvert.com/api/v2/convert)
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ImageFit {
+    #[serde(rename = "max")]
+    Max,
+    #[serde(rename = "crop")]
+    Crop,
+    #[serde(rename = "scale")]
+    Scale,
+    #[serde(rename = "upscale")]
+    Upscale,
+}
+
+/// Enum for the `video_codec` property of [`VideoOptions`].
+///
+/// Docs: [api/v2/convert](https://cloudconvert.com/api/v2/convert)
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum VideoCodec {
+    /// Copy the video stream without re-encoding.
+    #[serde(rename = "copy")]
+    Copy,
+    #[serde(rename = "x264")]
+    X264,
+    #[serde(rename = "x265")]
+    X265,
+    #[serde(rename = "vp8")]
+    Vp8,
+    #[serde(rename = "vp9")]
+    Vp9,
+    #[serde(rename = "av1")]
+    Av1,
+}
+
+/// Enum for the `preset` property of [`VideoOptions`].
+///
+/// Docs: [api/v2/convert](https://cloudconvert.com/api/v2/convert)
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum VideoPreset {
+    #[serde(rename = "ultrafast")]
+    UltraFast,
+    #[serde(rename = "superfast")]
+    SuperFast,
+    #[serde(rename = "veryfast")]
+    VeryFast,
+    #[serde(rename = "faster")]
+    Faster,
+    #[serde(rename = "fast")]
+    Fast,
+    #[serde(rename = "medium")]
+    Medium,
+    #[serde(rename = "slow")]
+    Slow,
+    #[serde(rename = "slower")]
+    Slower,
+    #[serde(rename = "veryslow")]
+    VerySlow,
+}
+
+/// Enum for the `audio_codec` property of [`VideoOptions`].
+///
+/// Docs: [api/v2/convert](https://cloudconvert.com/api/v2/convert)
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum AudioCodec {
+    /// Copy the audio stream without re-encoding.
+    #[serde(rename = "copy")]
+    Copy,
+    /// Remove the audio stream.
+    #[serde(rename = "none")]
+    Remove,
+    #[serde(rename = "aac")]
+    Aac,
+    #[serde(rename = "aacfdk")]
+    AacFdk,
+    #[serde(rename = "mp3")]
+    Mp3,
+    #[serde(rename = "opus")]
+    Opus,
+    #[serde(rename = "vorbis")]
+    Vorbis,
+}
+
 #[doc(hidden)]
 #[derive(Deserialize)]
 pub struct TasksOutput {
@@ -737,6 +1746,20 @@ impl From<TasksOutput> for Status {
     }
 }
 
+/// Cancel a task which is waiting or processing. If the task is part of a job, the job fails.
+///
+/// Docs: [api/v2/tasks](https://cloudconvert.com/api/v2/tasks#tasks-cancel)
+#[derive(Debug)]
+pub struct Cancel<'a> {
+    /// The ID of the task.
+    pub id: Cow<'a, str>,
+}
+
+bodyless_api_call!(
+    <'a> POST Cancel<'a> => TasksOutput as Status,
+    |call, endpoint| format!("{endpoint}/tasks/{}/cancel", call.id)
+);
+
 /// The status of a task.
 ///
 /// Docs: [api/v2/tasks](https://cloudconvert.com/api/v2/tasks#tasks-show)
@@ -773,12 +1796,14 @@ pub struct Status {
 
     /// If `status` is `Finished`, the number of credits consumed.
     #[serde(default)]
-    #[serde(rename = "code")]
     pub credits: Option<u16>,
 
     // TODO: started_at
     // TODO: ended_at
-    // TODO: depends_on_tasks
+    /// The IDs of the tasks this task depends on, for example its input tasks.
+    #[serde(default)]
+    pub depends_on_task_ids: Vec<String>,
+
     /// If this task is a retry, the original task ID.
     #[serde(default)]
     pub retry_of_task_id: Option<String>,
@@ -808,3 +1833,131 @@ pub struct Status {
     #[serde(default)]
     pub links: Option<HashMap<String, String>>,
 }
+
+impl Status {
+    /// If this is an [`ImportUpload`] task which is waiting for a file, returns the form which the
+    /// file should be uploaded to.
+    pub fn upload_form(&self) -> Option<UploadForm> {
+        let form = self.result.as_ref()?.get("form")?;
+        serde_json::from_value(form.clone()).ok()
+    }
+
+    /// Returns the files exported by the task, for example by a finished [`ExportUrl`] task.
+    pub fn exported_files(&self) -> Vec<ExportedFile> {
+        self.result
+            .as_ref()
+            .and_then(|result| result.get("files"))
+            .and_then(|files| serde_json::from_value(files.clone()).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// The form which a file should be uploaded to, for an [`ImportUpload`] task.
+///
+/// The file should be sent as a `multipart/form-data` POST request to `url`, including all the
+/// `parameters`, followed by the file in a field named `file`. [`UploadForm::multipart_body`]
+/// builds such a request body.
+///
+/// Docs: [api/v2/import#import-upload-tasks](https://cloudconvert.com/api/v2/import#import-upload-tasks)
+#[derive(Clone, Debug, Deserialize)]
+pub struct UploadForm {
+    pub url: String,
+    #[serde(default)]
+    pub parameters: serde_json::Map<String, serde_json::Value>,
+}
+
+impl UploadForm {
+    /// Build a `multipart/form-data` request body to upload `contents`, named `filename`.
+    ///
+    /// Returns the value for the `Content-Type` header (including the boundary), and the body.
+    pub fn multipart_body(&self, filename: &str, contents: &[u8]) -> (String, Vec<u8>) {
+        use sha2::Digest;
+
+        // The boundary must not appear within the body. Deriving it from the contents makes a
+        // collision unlikely, but the contents and parameters are still checked, and if it does
+        // appear, a new boundary is derived with a counter.
+        let mut attempt = 0u64;
+        let boundary = loop {
+            let mut hasher = sha2::Sha256::new();
+            hasher.update(contents);
+            if attempt > 0 {
+                hasher.update(attempt.to_le_bytes());
+            }
+            let boundary = format!("cloudconvert-rs-{}", hex::encode(&hasher.finalize()[..16]));
+            if !self.contains_delimiter(filename, contents, &boundary) {
+                break boundary;
+            }
+            attempt += 1;
+        };
+        let (mut body, tail) = self.multipart_parts(filename, &boundary);
+        body.reserve(contents.len() + tail.len());
+        body.extend_from_slice(contents);
+        body.extend_from_slice(&tail);
+        (format!("multipart/form-data; boundary={boundary}"), body)
+    }
+
+    /// Returns whether the delimiter for `boundary` appears in the contents, filename or
+    /// parameters.
+    fn contains_delimiter(&self, filename: &str, contents: &[u8], boundary: &str) -> bool {
+        let delimiter = format!("--{boundary}");
+        let delimiter = delimiter.as_bytes();
+        let contains = |haystack: &[u8]| haystack.windows(delimiter.len()).any(|w| w == delimiter);
+        contains(contents)
+            || contains(filename.as_bytes())
+            || self.parameters.iter().any(|(name, value)| {
+                contains(name.as_bytes())
+                    || match value {
+                        serde_json::Value::String(value) => contains(value.as_bytes()),
+                        value => contains(value.to_string().as_bytes()),
+                    }
+            })
+    }
+
+    /// Returns the parts of a `multipart/form-data` body which come before and after the contents
+    /// of the file, so the contents can be streamed between them.
+    pub(crate) fn multipart_parts(&self, filename: &str, boundary: &str) -> (Vec<u8>, Vec<u8>) {
+        let mut head = Vec::with_capacity(512);
+        let field_header = |head: &mut Vec<u8>, name: &str, filename: Option<&str>| {
+            head.extend_from_slice(format!("--{boundary}\r\n").as_bytes());
+            let name = name.replace('"', "%22");
+            match filename {
+                Some(filename) => head.extend_from_slice(
+                    format!(
+                        "Content-Disposition: form-data; name=\"{name}\"; filename=\"{}\"\r\n\
+                         Content-Type: application/octet-stream\r\n\r\n",
+                        filename.replace('"', "%22"),
+                    )
+                    .as_bytes(),
+                ),
+                None => head.extend_from_slice(
+                    format!("Content-Disposition: form-data; name=\"{name}\"\r\n\r\n").as_bytes(),
+                ),
+            }
+        };
+        for (name, value) in &self.parameters {
+            field_header(&mut head, name, None);
+            match value {
+                serde_json::Value::String(value) => head.extend_from_slice(value.as_bytes()),
+                value => head.extend_from_slice(value.to_string().as_bytes()),
+            }
+            head.extend_from_slice(b"\r\n");
+        }
+        field_header(&mut head, "file", Some(filename));
+        let tail = format!("\r\n--{boundary}--\r\n").into_bytes();
+        (head, tail)
+    }
+}
+
+/// A file exported by a task, such as an [`ExportUrl`] task.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ExportedFile {
+    pub filename: String,
+
+    /// The URL the file can be downloaded from.
+    #[serde(default)]
+    pub url: Option<String>,
+
+    /// The size of the file, in bytes.
+    #[serde(default)]
+    pub size: Option<u64>,
+}
@@ -0,0 +1,1078 @@
+//! The mock CloudConvert API server.
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, StatusCode};
+use serde_json::{json, Value};
+
+use crate::job::Job;
+use crate::HyperClient;
+
+/// A mock CloudConvert API server, listening on a local port.
+///
+/// The server is shut down when this is dropped.
+pub struct MockServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    shutdown: Option<tokio::sync::oneshot::Sender<()>>,
+}
+
+/// A webhook sent by a [`MockServer`].
+#[derive(Clone, Debug)]
+pub struct SentWebhook {
+    /// The URL the webhook was sent to.
+    pub url: String,
+
+    /// The event name, for example `job.finished`.
+    pub event: String,
+
+    /// The JSON body of the webhook.
+    pub body: Vec<u8>,
+
+    /// The `CloudConvert-Signature` header sent with the webhook.
+    pub signature: String,
+}
+
+/// A file held by a task in a [`MockServer`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MockFile {
+    pub filename: String,
+    pub contents: Vec<u8>,
+}
+
+impl MockServer {
+    /// The default secret used to sign webhooks. See [`MockServer::set_signing_secret`].
+    pub const SIGNING_SECRET: &'static [u8] = b"mock-signing-secret";
+
+    /// The bearer token used by [`MockServer::client`]. The server accepts any bearer token.
+    pub const TOKEN: &'static str = "mock-token";
+
+    /// Start a server on a free local port.
+    ///
+    /// This must be called from within a tokio runtime.
+    pub async fn start() -> MockServer {
+        let state = Arc::new(Mutex::new(State {
+            signing_secret: MockServer::SIGNING_SECRET.to_vec(),
+            auto_advance: true,
+            ..State::default()
+        }));
+
+        let service_state = state.clone();
+        let make_service = make_service_fn(move |_| {
+            let state = service_state.clone();
+            async move { Ok::<_, Infallible>(service_fn(move |req| handle(state.clone(), req))) }
+        });
+        let server = hyper::Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+        let addr = server.local_addr();
+        state.lock().unwrap().base_url = format!("http://{addr}");
+
+        let (shutdown, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+        tokio::spawn(server.with_graceful_shutdown(async {
+            shutdown_rx.await.ok();
+        }));
+
+        MockServer {
+            addr,
+            state,
+            shutdown: Some(shutdown),
+        }
+    }
+
+    /// The API endpoint of the server, for example `http://127.0.0.1:1234/v2`.
+    pub fn endpoint(&self) -> String {
+        format!("http://{}/v2", self.addr)
+    }
+
+    /// The synchronous API endpoint of the server, for [`job::CreateSync`](crate::job::CreateSync).
+    /// Jobs created or shown using this endpoint are completed before the server responds.
+    pub fn sync_endpoint(&self) -> String {
+        format!("http://{}/sync/v2", self.addr)
+    }
+
+    /// Create a client which calls this server.
+    pub fn client(&self) -> HyperClient {
+        let mut client = HyperClient::default_client(MockServer::TOKEN);
+        client.client.endpoint = self.endpoint().into();
+        client
+    }
+
+    /// Set the secret used to sign webhooks.
+    pub fn set_signing_secret(&self, secret: impl Into<Vec<u8>>) {
+        self.state.lock().unwrap().signing_secret = secret.into();
+    }
+
+    /// Set whether jobs (and tasks) advance to their next state each time they are shown or listed.
+    ///
+    /// This is enabled by default, so polling a job eventually finishes it. If it's disabled, jobs
+    /// only advance using [`MockServer::advance_job`] and [`MockServer::complete_job`], or the
+    /// synchronous API at [`MockServer::sync_endpoint`].
+    pub fn set_auto_advance(&self, auto_advance: bool) {
+        self.state.lock().unwrap().auto_advance = auto_advance;
+    }
+
+    /// Make every task with `operation` (for example `convert`) fail, with the given error code
+    /// and message.
+    pub fn fail_operation(
+        &self,
+        operation: impl Into<String>,
+        code: impl Into<String>,
+        message: impl Into<String>,
+    ) {
+        self.state
+            .lock()
+            .unwrap()
+            .failures
+            .insert(operation.into(), (code.into(), message.into()));
+    }
+
+    /// Stop failing the operations passed to [`MockServer::fail_operation`].
+    pub fn clear_failures(&self) {
+        self.state.lock().unwrap().failures.clear();
+    }
+
+    /// Set the contents of the file imported by an [`crate::task::ImportUrl`] task for `url`.
+    pub fn add_url_file(&self, url: impl Into<String>, contents: impl Into<Vec<u8>>) {
+        self.state
+            .lock()
+            .unwrap()
+            .url_files
+            .insert(url.into(), contents.into());
+    }
+
+    /// Advance a job to its next state, returning `false` if the job doesn't exist or can't
+    /// advance (because it has already finished, or is waiting for an upload).
+    pub fn advance_job(&self, id: &str) -> bool {
+        let (advanced, webhooks) = {
+            let mut state = self.state.lock().unwrap();
+            let advanced = state.advance_job(id);
+            (advanced, state.take_pending_webhooks())
+        };
+        send_webhooks(webhooks);
+        advanced
+    }
+
+    /// Advance a job until it finishes, fails, or can't advance (because it's waiting for an
+    /// upload).
+    pub fn complete_job(&self, id: &str) {
+        let webhooks = {
+            let mut state = self.state.lock().unwrap();
+            state.complete_job(id);
+            state.take_pending_webhooks()
+        };
+        send_webhooks(webhooks);
+    }
+
+    /// Returns the IDs of all the jobs, in the order they were created.
+    pub fn job_ids(&self) -> Vec<String> {
+        let state = self.state.lock().unwrap();
+        state.jobs.iter().map(|job| job.id.clone()).collect()
+    }
+
+    /// Returns a job, as it would be returned by the API.
+    pub fn job(&self, id: &str) -> Option<Job> {
+        let state = self.state.lock().unwrap();
+        let job = state.job_json(id)?;
+        Some(serde_json::from_value(job).unwrap())
+    }
+
+    /// Returns the files held by a task: the uploaded or imported files of an import task, or the
+    /// output files of any other task.
+    pub fn task_files(&self, task_id: &str) -> Vec<MockFile> {
+        let state = self.state.lock().unwrap();
+        state
+            .tasks
+            .get(task_id)
+            .map(|task| task.files.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns every webhook sent by the server, in the order they were sent.
+    ///
+    /// Webhooks are recorded even if they couldn't be delivered.
+    pub fn webhooks(&self) -> Vec<SentWebhook> {
+        self.state.lock().unwrap().webhooks.clone()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            shutdown.send(()).ok();
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MockStatus {
+    Waiting,
+    Processing,
+    Finished,
+    Error,
+}
+
+impl MockStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            MockStatus::Waiting => "waiting",
+            MockStatus::Processing => "processing",
+            MockStatus::Finished => "finished",
+            MockStatus::Error => "error",
+        }
+    }
+
+    fn is_done(self) -> bool {
+        matches!(self, MockStatus::Finished | MockStatus::Error)
+    }
+}
+
+struct MockTask {
+    id: String,
+    job_id: Option<String>,
+    name: Option<String>,
+    operation: String,
+    status: MockStatus,
+    message: Option<String>,
+    code: Option<String>,
+    payload: Value,
+    files: Vec<MockFile>,
+    depends_on: Vec<String>,
+}
+
+struct MockJob {
+    id: String,
+    tag: Option<String>,
+    status: MockStatus,
+    task_ids: Vec<String>,
+    webhook_url: Option<String>,
+}
+
+#[derive(Default)]
+struct State {
+    base_url: String,
+    next_id: u64,
+    jobs: Vec<MockJob>,
+    tasks: HashMap<String, MockTask>,
+    signing_secret: Vec<u8>,
+    auto_advance: bool,
+    failures: HashMap<String, (String, String)>,
+    url_files: HashMap<String, Vec<u8>>,
+    webhooks: Vec<SentWebhook>,
+    pending_webhooks: Vec<SentWebhook>,
+}
+
+/// An error response.
+struct ApiError(StatusCode, &'static str, String);
+
+impl ApiError {
+    fn not_found(what: &str) -> ApiError {
+        ApiError(
+            StatusCode::NOT_FOUND,
+            "NOT_FOUND",
+            format!("{what} not found"),
+        )
+    }
+
+    fn invalid(message: impl Into<String>) -> ApiError {
+        ApiError(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "INVALID_DATA",
+            message.into(),
+        )
+    }
+}
+
+impl State {
+    fn new_id(&mut self, prefix: &str) -> String {
+        self.next_id += 1;
+        format!("{prefix}-{:08}", self.next_id)
+    }
+
+    fn job(&self, id: &str) -> Option<&MockJob> {
+        self.jobs.iter().find(|job| job.id == id)
+    }
+
+    fn job_mut(&mut self, id: &str) -> Option<&mut MockJob> {
+        self.jobs.iter_mut().find(|job| job.id == id)
+    }
+
+    /// Create a job from a `POST /jobs` request body.
+    fn create_job(&mut self, body: &Value) -> Result<String, ApiError> {
+        let tasks = body
+            .get("tasks")
+            .and_then(Value::as_object)
+            .filter(|tasks| !tasks.is_empty())
+            .ok_or_else(|| ApiError::invalid("The tasks field is required."))?;
+        let job_id = self.new_id("job");
+
+        // Assign the IDs first, so inputs can reference tasks in any order.
+        let mut ids = HashMap::new();
+        for name in tasks.keys() {
+            let id = self.new_id("task");
+            ids.insert(name.clone(), id);
+        }
+        let mut new_tasks = Vec::with_capacity(tasks.len());
+        for (name, payload) in tasks {
+            let operation = payload
+                .get("operation")
+                .and_then(Value::as_str)
+                .ok_or_else(|| ApiError::invalid(format!("Task {name} has no operation.")))?;
+            let depends_on = input_names(payload)
+                .into_iter()
+                .map(|input| {
+                    ids.get(&input)
+                        .cloned()
+                        .or_else(|| self.tasks.contains_key(&input).then_some(input.clone()))
+                        .ok_or_else(|| {
+                            ApiError::invalid(format!("Input task {input} of {name} not found."))
+                        })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let mut payload = payload.clone();
+            payload.as_object_mut().unwrap().remove("operation");
+            new_tasks.push(MockTask {
+                id: ids[name].clone(),
+                job_id: Some(job_id.clone()),
+                name: Some(name.clone()),
+                operation: operation.to_string(),
+                status: MockStatus::Waiting,
+                message: None,
+                code: None,
+                payload,
+                files: Vec::new(),
+                depends_on,
+            });
+        }
+
+        let task_ids = new_tasks.iter().map(|task| task.id.clone()).collect();
+        for task in new_tasks {
+            self.tasks.insert(task.id.clone(), task);
+        }
+        self.jobs.push(MockJob {
+            id: job_id.clone(),
+            tag: body.get("tag").and_then(Value::as_str).map(str::to_string),
+            status: MockStatus::Waiting,
+            task_ids,
+            webhook_url: body
+                .get("webhook_url")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+        });
+        self.queue_webhook(&job_id, "job.created");
+        Ok(job_id)
+    }
+
+    /// Create a task which isn't part of a job, from a `POST /{operation}` request.
+    fn create_task(&mut self, operation: String, payload: Value) -> Result<String, ApiError> {
+        if !payload.is_object() {
+            return Err(ApiError::invalid("The request body must be an object."));
+        }
+        let depends_on = input_names(&payload);
+        if let Some(input) = depends_on.iter().find(|id| !self.tasks.contains_key(*id)) {
+            return Err(ApiError::invalid(format!("Input task {input} not found.")));
+        }
+        let id = self.new_id("task");
+        self.tasks.insert(
+            id.clone(),
+            MockTask {
+                id: id.clone(),
+                job_id: None,
+                name: None,
+                operation,
+                status: MockStatus::Waiting,
+                message: None,
+                code: None,
+                payload,
+                files: Vec::new(),
+                depends_on,
+            },
+        );
+        Ok(id)
+    }
+
+    /// Advance a job to its next state. Returns `true` if anything changed.
+    fn advance_job(&mut self, id: &str) -> bool {
+        let Some(job) = self.job(id) else {
+            return false;
+        };
+        let task_ids = job.task_ids.clone();
+        match job.status {
+            MockStatus::Waiting => {
+                self.job_mut(id).unwrap().status = MockStatus::Processing;
+                for task_id in &task_ids {
+                    self.start_task(task_id);
+                }
+                true
+            }
+            MockStatus::Processing => {
+                let advanced = self.run_tasks(&task_ids);
+                let statuses: Vec<MockStatus> =
+                    task_ids.iter().map(|id| self.tasks[id].status).collect();
+                let status = if statuses
+                    .iter()
+                    .all(|status| *status == MockStatus::Finished)
+                {
+                    MockStatus::Finished
+                } else if statuses.iter().all(|status| status.is_done()) {
+                    MockStatus::Error
+                } else {
+                    MockStatus::Processing
+                };
+                if status != MockStatus::Processing {
+                    self.job_mut(id).unwrap().status = status;
+                    let event = match status {
+                        MockStatus::Finished => "job.finished",
+                        _ => "job.failed",
+                    };
+                    self.queue_webhook(id, event);
+                }
+                advanced || status != MockStatus::Processing
+            }
+            MockStatus::Finished | MockStatus::Error => false,
+        }
+    }
+
+    fn complete_job(&mut self, id: &str) {
+        while self.advance_job(id) {}
+    }
+
+    /// Advance a task which isn't part of a job to its next state.
+    fn advance_task(&mut self, id: &str) -> bool {
+        match self.tasks.get(id).map(|task| task.status) {
+            Some(MockStatus::Waiting) => self.start_task(id),
+            Some(MockStatus::Processing) => self.run_tasks(&[id.to_string()]),
+            _ => false,
+        }
+    }
+
+    /// Move a task from waiting to processing, unless it's waiting for an upload.
+    fn start_task(&mut self, id: &str) -> bool {
+        let task = self.tasks.get_mut(id).unwrap();
+        if task.status == MockStatus::Waiting && task.operation != "import/upload" {
+            task.status = MockStatus::Processing;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Run every processing task whose inputs have finished, until no more can be run.
+    fn run_tasks(&mut self, ids: &[String]) -> bool {
+        let mut advanced = false;
+        loop {
+            let mut ran = false;
+            for id in ids {
+                let task = &self.tasks[id];
+                if task.status != MockStatus::Processing {
+                    continue;
+                }
+                let inputs: Vec<MockStatus> = task
+                    .depends_on
+                    .iter()
+                    .map(|input| {
+                        self.tasks
+                            .get(input)
+                            .map_or(MockStatus::Error, |t| t.status)
+                    })
+                    .collect();
+                if inputs.contains(&MockStatus::Error) {
+                    let task = self.tasks.get_mut(id).unwrap();
+                    task.status = MockStatus::Error;
+                    task.code = Some("INPUT_TASK_FAILED".to_string());
+                    task.message = Some("Input task has failed".to_string());
+                    ran = true;
+                } else if inputs.iter().all(|status| *status == MockStatus::Finished) {
+                    self.run_task(id);
+                    ran = true;
+                }
+            }
+            if !ran {
+                return advanced;
+            }
+            advanced = true;
+        }
+    }
+
+    /// Run a task, whose inputs have all finished.
+    fn run_task(&mut self, id: &str) {
+        let task = &self.tasks[id];
+        if let Some((code, message)) = self.failures.get(&task.operation) {
+            let (code, message) = (code.clone(), message.clone());
+            let task = self.tasks.get_mut(id).unwrap();
+            task.status = MockStatus::Error;
+            task.code = Some(code);
+            task.message = Some(message);
+            return;
+        }
+
+        let payload_str = |field: &str| task.payload.get(field).and_then(Value::as_str);
+        let input_files: Vec<MockFile> = task
+            .depends_on
+            .iter()
+            .flat_map(|input| self.tasks[input].files.iter().cloned())
+            .collect();
+        let files = match task.operation.as_str() {
+            "import/url" => {
+                let url = payload_str("url").unwrap_or_default();
+                let filename = payload_str("filename")
+                    .map(str::to_string)
+                    .unwrap_or_else(|| {
+                        let path = url.split(['?', '#']).next().unwrap_or_default();
+                        match path.rsplit('/').next() {
+                            Some(name) if !name.is_empty() && path.contains("://") => {
+                                name.to_string()
+                            }
+                            _ => "file".to_string(),
+                        }
+                    });
+                let contents = self
+                    .url_files
+                    .get(url)
+                    .cloned()
+                    .unwrap_or_else(|| format!("mock contents of {url}").into_bytes());
+                vec![MockFile { filename, contents }]
+            }
+            "import/raw" => vec![MockFile {
+                filename: payload_str("filename").unwrap_or("file").to_string(),
+                contents: payload_str("file").unwrap_or_default().as_bytes().to_vec(),
+            }],
+            operation if operation.starts_with("import/") => vec![MockFile {
+                filename: payload_str("filename")
+                    .or(payload_str("file"))
+                    .or(payload_str("key"))
+                    .or(payload_str("blob"))
+                    .and_then(|name| name.rsplit('/').next())
+                    .unwrap_or("file")
+                    .to_string(),
+                contents: format!("mock contents imported by {operation}").into_bytes(),
+            }],
+            operation if operation.starts_with("export/") => input_files,
+            // Every other task outputs its input files, renamed to the output format.
+            _ => match payload_str("output_format") {
+                Some(format) => input_files
+                    .into_iter()
+                    .map(|file| MockFile {
+                        filename: match file.filename.rsplit_once('.') {
+                            Some((stem, _)) => format!("{stem}.{format}"),
+                            None => format!("{}.{format}", file.filename),
+                        },
+                        contents: file.contents,
+                    })
+                    .collect(),
+                None => input_files,
+            },
+        };
+        let task = self.tasks.get_mut(id).unwrap();
+        task.files = files;
+        task.status = MockStatus::Finished;
+    }
+
+    /// Store an uploaded file for an `import/upload` task.
+    fn upload(&mut self, task_id: &str, file: MockFile) -> Result<(), ApiError> {
+        let task = self
+            .tasks
+            .get_mut(task_id)
+            .filter(|task| task.operation == "import/upload")
+            .ok_or_else(|| ApiError::not_found("Upload task"))?;
+        if task.status.is_done() {
+            return Err(ApiError::invalid("The file has already been uploaded."));
+        }
+        task.files = vec![file];
+        task.status = MockStatus::Finished;
+        Ok(())
+    }
+
+    /// Cancel a waiting or processing task. If it's part of a job, the job fails once all its
+    /// tasks are done, without running any more of them.
+    fn cancel_task(&mut self, id: &str) -> Result<(), ApiError> {
+        let task = self
+            .tasks
+            .get_mut(id)
+            .ok_or_else(|| ApiError::not_found("Task"))?;
+        if task.status.is_done() {
+            return Err(ApiError::invalid("The task has already finished."));
+        }
+        task.status = MockStatus::Error;
+        task.code = Some("CANCELLED".to_string());
+        task.message = Some("Task has been cancelled".to_string());
+        let Some(job_id) = task.job_id.clone() else {
+            return Ok(());
+        };
+        let job = self.job(&job_id).unwrap();
+        if job
+            .task_ids
+            .iter()
+            .all(|id| self.tasks[id].status.is_done())
+        {
+            self.job_mut(&job_id).unwrap().status = MockStatus::Error;
+            self.queue_webhook(&job_id, "job.failed");
+        }
+        Ok(())
+    }
+
+    fn delete_job(&mut self, id: &str) -> bool {
+        let Some(idx) = self.jobs.iter().position(|job| job.id == id) else {
+            return false;
+        };
+        let job = self.jobs.remove(idx);
+        for task_id in job.task_ids {
+            self.tasks.remove(&task_id);
+        }
+        true
+    }
+
+    fn task_json(&self, id: &str) -> Option<Value> {
+        let task = self.tasks.get(id)?;
+        let base = &self.base_url;
+        let result = if task.operation == "import/upload" && !task.status.is_done() {
+            json!({
+                "form": {
+                    "url": format!("{base}/upload/{}", task.id),
+                    "parameters": {
+                        "expires": 1893456000,
+                        "signature": format!("mock-signature-{}", task.id),
+                    },
+                },
+            })
+        } else if task.status == MockStatus::Finished {
+            let files: Vec<Value> = task
+                .files
+                .iter()
+                .enumerate()
+                .map(|(idx, file)| {
+                    let mut value = json!({
+                        "filename": file.filename,
+                        "size": file.contents.len(),
+                    });
+                    if task.operation == "export/url" {
+                        let mut url = format!("{base}/download/{}/{idx}/", task.id);
+                        crate::percent_encode(&file.filename, &mut url);
+                        value["url"] = Value::String(url);
+                    }
+                    value
+                })
+                .collect();
+            json!({ "files": files })
+        } else {
+            Value::Null
+        };
+        Some(json!({
+            "id": task.id,
+            "job_id": task.job_id,
+            "name": task.name,
+            "operation": task.operation,
+            "status": task.status.as_str(),
+            "message": task.message,
+            "code": task.code,
+            // Import and export tasks are free, every other task consumes one credit.
+            "credits": (task.status == MockStatus::Finished).then_some(
+                match task.operation.split('/').next() {
+                    Some("import" | "export") => 0,
+                    _ => 1,
+                },
+            ),
+            "engine": null,
+            "engine_version": null,
+            "depends_on_task_ids": task.depends_on,
+            "payload": task.payload,
+            "result": result,
+            "links": {
+                "self": format!("{base}/v2/tasks/{}", task.id),
+            },
+        }))
+    }
+
+    fn job_json(&self, id: &str) -> Option<Value> {
+        let job = self.job(id)?;
+        let tasks: Vec<Value> = job
+            .task_ids
+            .iter()
+            .filter_map(|task_id| self.task_json(task_id))
+            .collect();
+        Some(json!({
+            "id": job.id,
+            "tag": job.tag,
+            "status": job.status.as_str(),
+            "tasks": tasks,
+            "links": {
+                "self": format!("{}/v2/jobs/{}", self.base_url, job.id),
+            },
+        }))
+    }
+
+    /// Queue a webhook for a job event, if the job has a webhook URL.
+    fn queue_webhook(&mut self, job_id: &str, event: &str) {
+        use hmac::{Hmac, Mac};
+
+        let Some(url) = self.job(job_id).and_then(|job| job.webhook_url.clone()) else {
+            return;
+        };
+        let body = serde_json::to_vec(&json!({
+            "event": event,
+            "job": self.job_json(job_id).unwrap(),
+        }))
+        .unwrap();
+        let mut mac = Hmac::<sha2::Sha256>::new_from_slice(&self.signing_secret).unwrap();
+        mac.update(&body);
+        let webhook = SentWebhook {
+            url,
+            event: event.to_string(),
+            body,
+            signature: hex::encode(mac.finalize().into_bytes()),
+        };
+        self.webhooks.push(webhook.clone());
+        self.pending_webhooks.push(webhook);
+    }
+
+    fn take_pending_webhooks(&mut self) -> Vec<SentWebhook> {
+        std::mem::take(&mut self.pending_webhooks)
+    }
+}
+
+/// Returns the names (or IDs) of the input tasks of a task payload.
+fn input_names(payload: &Value) -> Vec<String> {
+    let mut names = Vec::new();
+    for field in ["input", "image"] {
+        match payload.get(field) {
+            Some(Value::String(name)) if field == "input" || !name.contains("://") => {
+                names.push(name.clone())
+            }
+            Some(Value::Array(items)) => {
+                names.extend(items.iter().filter_map(Value::as_str).map(str::to_string))
+            }
+            _ => {}
+        }
+    }
+    names
+}
+
+/// Deliver webhooks in the background. Delivery failures are ignored.
+fn send_webhooks(webhooks: Vec<SentWebhook>) {
+    let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+        return;
+    };
+    for webhook in webhooks {
+        runtime.spawn(async move {
+            let Ok(request) = Request::post(&webhook.url)
+                .header(hyper::header::CONTENT_TYPE, "application/json")
+                .header("CloudConvert-Signature", &webhook.signature)
+                .body(Body::from(webhook.body))
+            else {
+                return;
+            };
+            hyper::Client::new().request(request).await.ok();
+        });
+    }
+}
+
+fn json_response(status: StatusCode, body: &Value) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header(hyper::header::CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_vec(body).unwrap()))
+        .unwrap()
+}
+
+fn error_response(ApiError(status, code, message): ApiError) -> Response<Body> {
+    json_response(status, &json!({ "message": message, "code": code }))
+}
+
+async fn handle(
+    state: Arc<Mutex<State>>,
+    req: Request<Body>,
+) -> Result<Response<Body>, Infallible> {
+    let (parts, body) = req.into_parts();
+    let body = hyper::body::to_bytes(body).await.unwrap_or_default();
+    let (response, webhooks) = {
+        let mut state = state.lock().unwrap();
+        let response = route(&mut state, &parts, &body).unwrap_or_else(error_response);
+        (response, state.take_pending_webhooks())
+    };
+    send_webhooks(webhooks);
+    Ok(response)
+}
+
+fn route(
+    state: &mut State,
+    parts: &hyper::http::request::Parts,
+    body: &[u8],
+) -> Result<Response<Body>, ApiError> {
+    let path: Vec<String> = parts
+        .uri
+        .path()
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(percent_decode)
+        .collect();
+    let path: Vec<&str> = path.iter().map(String::as_str).collect();
+    let query = parse_query(parts.uri.query().unwrap_or_default());
+
+    // Uploads and downloads are signed URLs, so they don't need the bearer token.
+    match (&parts.method, path.as_slice()) {
+        (&Method::POST, ["upload", task_id]) => {
+            let content_type = parts
+                .headers
+                .get(hyper::header::CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or_default();
+            let file = parse_multipart_file(content_type, body)
+                .ok_or_else(|| ApiError::invalid("The file field is required."))?;
+            state.upload(task_id, file)?;
+            return Ok(Response::builder()
+                .status(StatusCode::CREATED)
+                .body(Body::empty())
+                .unwrap());
+        }
+        (&Method::GET, ["download", task_id, idx, ..]) => {
+            let file = state
+                .tasks
+                .get(*task_id)
+                .filter(|task| task.operation == "export/url")
+                .and_then(|task| task.files.get(idx.parse::<usize>().ok()?))
+                .ok_or_else(|| ApiError::not_found("File"))?;
+            return Ok(Response::builder()
+                .header(hyper::header::CONTENT_TYPE, "application/octet-stream")
+                .header(
+                    hyper::header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"{}\"", file.filename),
+                )
+                .body(Body::from(file.contents.clone()))
+                .unwrap());
+        }
+        (_, ["v2", ..] | ["sync", "v2", ..]) => {}
+        _ => return Err(ApiError::not_found("Endpoint")),
+    }
+    // The synchronous API doesn't respond until jobs have completed.
+    let sync = path[0] == "sync";
+    let path = &path[usize::from(sync)..];
+
+    let authorized = parts
+        .headers
+        .get(hyper::header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|token| !token.is_empty());
+    if !authorized {
+        return Err(ApiError(
+            StatusCode::UNAUTHORIZED,
+            "UNAUTHENTICATED",
+            "Unauthenticated.".to_string(),
+        ));
+    }
+
+    let parse_body = || {
+        serde_json::from_slice::<Value>(body)
+            .map_err(|err| ApiError::invalid(format!("Invalid JSON: {err}")))
+    };
+    let show_job = |state: &State, id: &str| {
+        let job = state
+            .job_json(id)
+            .ok_or_else(|| ApiError::not_found("Job"))?;
+        Ok(json_response(StatusCode::OK, &json!({ "data": job })))
+    };
+    let show_task = |state: &State, id: &str| {
+        let task = state
+            .task_json(id)
+            .ok_or_else(|| ApiError::not_found("Task"))?;
+        Ok(json_response(StatusCode::OK, &json!({ "data": task })))
+    };
+    let no_content = || {
+        Ok(Response::builder()
+            .status(StatusCode::NO_CONTENT)
+            .body(Body::empty())
+            .unwrap())
+    };
+
+    match (&parts.method, &path[1..]) {
+        (&Method::POST, ["jobs"]) => {
+            let id = state.create_job(&parse_body()?)?;
+            if sync {
+                state.complete_job(&id);
+            }
+            let job = state.job_json(&id).unwrap();
+            Ok(json_response(StatusCode::CREATED, &json!({ "data": job })))
+        }
+        (&Method::GET, ["jobs"]) => {
+            let status = query.get("filter[status]");
+            let tag = query.get("filter[tag]");
+            let per_page = query
+                .get("per_page")
+                .and_then(|n| n.parse::<usize>().ok())
+                .unwrap_or(100)
+                .max(1);
+            let page = query
+                .get("page")
+                .and_then(|n| n.parse::<usize>().ok())
+                .unwrap_or(1)
+                .max(1);
+            let matching: Vec<String> = state
+                .jobs
+                .iter()
+                .rev()
+                .filter(|job| status.is_none_or(|status| job.status.as_str() == status))
+                .filter(|job| tag.is_none_or(|tag| job.tag.as_ref() == Some(tag)))
+                .map(|job| job.id.clone())
+                .collect();
+            let ids = matching.iter().skip((page - 1) * per_page).take(per_page);
+            if state.auto_advance {
+                for id in ids.clone() {
+                    state.advance_job(id);
+                }
+            }
+            // Like the real API, tasks are only listed if they're included.
+            let include_tasks = query
+                .get("include")
+                .is_some_and(|include| include.split(',').any(|name| name == "tasks"));
+            let jobs: Vec<Value> = ids
+                .filter_map(|id| state.job_json(id))
+                .map(|mut job| {
+                    if !include_tasks {
+                        job.as_object_mut().unwrap().remove("tasks");
+                    }
+                    job
+                })
+                .collect();
+            let next = (matching.len() > page * per_page).then(|| {
+                format!(
+                    "{}/v2/jobs?page={}&per_page={per_page}",
+                    state.base_url,
+                    page + 1
+                )
+            });
+            Ok(json_response(
+                StatusCode::OK,
+                &json!({
+                    "data": jobs,
+                    "links": { "next": next },
+                    "meta": { "current_page": page, "per_page": per_page },
+                }),
+            ))
+        }
+        (&Method::GET, ["jobs", id]) => {
+            if sync {
+                state.complete_job(id);
+            } else if state.auto_advance {
+                state.advance_job(id);
+            }
+            show_job(state, id)
+        }
+        (&Method::DELETE, ["jobs", id]) => match state.delete_job(id) {
+            true => no_content(),
+            false => Err(ApiError::not_found("Job")),
+        },
+        (&Method::GET, ["tasks", id]) => {
+            if state.auto_advance {
+                match state.tasks.get(*id).and_then(|task| task.job_id.clone()) {
+                    Some(job_id) => state.advance_job(&job_id),
+                    None => state.advance_task(id),
+                };
+            }
+            show_task(state, id)
+        }
+        (&Method::POST, ["tasks", id, "cancel"]) => {
+            state.cancel_task(id)?;
+            show_task(state, id)
+        }
+        (&Method::DELETE, ["tasks", id]) => match state.tasks.remove(*id) {
+            Some(task) => {
+                if let Some(job) = task.job_id.and_then(|job_id| state.job_mut(&job_id)) {
+                    job.task_ids.retain(|task_id| task_id != id);
+                }
+                no_content()
+            }
+            None => Err(ApiError::not_found("Task")),
+        },
+        (&Method::POST, operation) if !operation.is_empty() && operation[0] != "tasks" => {
+            let id = state.create_task(operation.join("/"), parse_body()?)?;
+            let task = state.task_json(&id).unwrap();
+            Ok(json_response(StatusCode::CREATED, &json!({ "data": task })))
+        }
+        _ => Err(ApiError::not_found("Endpoint")),
+    }
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+    while idx < bytes.len() {
+        let hex = bytes
+            .get(idx + 1..idx + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[idx], hex) {
+            (b'%', Some(byte)) => {
+                out.push(byte);
+                idx += 3;
+            }
+            (b'+', _) => {
+                out.push(b' ');
+                idx += 1;
+            }
+            (byte, _) => {
+                out.push(byte);
+                idx += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter(|param| !param.is_empty())
+        .map(|param| {
+            let (name, value) = param.split_once('=').unwrap_or((param, ""));
+            (percent_decode(name), percent_decode(value))
+        })
+        .collect()
+}
+
+/// Find the `file` field of a `multipart/form-data` body.
+fn parse_multipart_file(content_type: &str, body: &[u8]) -> Option<MockFile> {
+    fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+        haystack
+            .windows(needle.len())
+            .position(|window| window == needle)
+    }
+
+    let boundary = content_type
+        .split(';')
+        .find_map(|param| param.trim().strip_prefix("boundary="))?
+        .trim_matches('"');
+    let delimiter = format!("--{boundary}");
+    let mut rest = &body[find(body, delimiter.as_bytes())? + delimiter.len()..];
+    loop {
+        // The final delimiter is followed by `--`.
+        let part = rest.strip_prefix(b"\r\n")?;
+        let end = find(part, format!("\r\n{delimiter}").as_bytes())?;
+        let (part, next) = (&part[..end], &part[end + 2 + delimiter.len()..]);
+        let header_end = find(part, b"\r\n\r\n")?;
+        let headers = std::str::from_utf8(&part[..header_end]).ok()?;
+        let disposition = headers.split("\r\n").find(|header| {
+            header
+                .to_ascii_lowercase()
+                .starts_with("content-disposition:")
+        })?;
+        let param = |name: &str| {
+            disposition.split(';').find_map(|param| {
+                let (param_name, value) = param.trim().split_once('=')?;
+                (param_name == name).then(|| value.trim_matches('"').to_string())
+            })
+        };
+        if param("name").as_deref() == Some("file") {
+            return Some(MockFile {
+                filename: param("filename").unwrap_or_else(|| "file".to_string()),
+                contents: part[header_end + 4..].to_vec(),
+            });
+        }
+        if next.starts_with(b"--") {
+            return None;
+        }
+        rest = next;
+    }
+}
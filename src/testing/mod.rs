@@ -0,0 +1,64 @@
+//! Tools for testing code which uses this crate, without network access.
+//!
+//! This module requires the `testing` feature.
+//!
+//! There are two tools:
+//!
+//! - [`MockServer`]: an in-process mock of the CloudConvert API, for integration tests.
+//! - [`RecordingTransport`]: a transport which records requests, rather than sending them, for
+//!   unit testing the requests made.
+//!
+//! # Mock server
+//!
+//! [`MockServer`] serves the job and task endpoints, upload forms for [`ImportUpload`] tasks, and
+//! downloads for [`ExportUrl`] tasks. Jobs move through the same states as they do on the real
+//! API (`waiting`, then `processing`, then `finished` or `error`), and signed webhooks are sent
+//! when a job is created, finishes or fails. The synchronous API is served at
+//! [`MockServer::sync_endpoint`].
+//!
+//! The mock doesn't really convert anything: a convert task outputs its input files, renamed to
+//! the output format. Import tasks output the uploaded file, the file added with
+//! [`MockServer::add_url_file`], or a placeholder.
+//!
+//! ```no_run
+//! use cloudconvert::testing::MockServer;
+//! # use cloudconvert::job;
+//!
+//! # async fn example(create_job: job::Create<'_>) -> Result<(), cloudconvert::Error> {
+//! let server = MockServer::start().await;
+//! let client = server.client();
+//! let job = client.call(create_job).await?;
+//! server.complete_job(&job.id);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Recording requests
+//!
+//! A [`RecordingTransport`] captures the method, URI, headers and body of every request, and
+//! returns canned responses:
+//!
+//! ```no_run
+//! use cloudconvert::testing::RecordingTransport;
+//! use hapic::http::Method;
+//! use serde_json::json;
+//! # use cloudconvert::job;
+//!
+//! # async fn example(create_job: job::Create<'_>) -> Result<(), cloudconvert::Error> {
+//! let transport = RecordingTransport::new();
+//! let client = transport.client();
+//! client.call(create_job).await?;
+//! transport.assert_request(0, Method::POST, "/v2/jobs");
+//! transport.assert_json_body(0, &json!({ "tasks": {} }));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`ImportUpload`]: crate::task::ImportUpload
+//! [`ExportUrl`]: crate::task::ExportUrl
+
+mod mock;
+mod recording;
+
+pub use mock::{MockFile, MockServer, SentWebhook};
+pub use recording::{CannedResponse, RecordedRequest, RecordingClient, RecordingTransport};
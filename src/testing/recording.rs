@@ -0,0 +1,286 @@
+//! A transport which records requests instead of sending them.
+
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+
+use hapic::http::{HeaderMap, Method, Request, Response, StatusCode};
+use serde_json::{json, Value};
+
+use crate::Client;
+
+/// A [`Client`] using a [`RecordingTransport`].
+pub type RecordingClient = Client<Vec<u8>, RecordingTransport>;
+
+/// A transport which records every request made through it, and returns canned responses,
+/// without making any network requests.
+///
+/// Responses queued with [`RecordingTransport::push_response`] are returned in order. Once the
+/// queue is empty, the transport runs in "dry-run" mode: creating a job (or a task) returns a
+/// `waiting` job (or task) built from the request, and every other request returns a `404`.
+///
+/// The transport can be cloned, and all the clones share the same recording, so one clone can be
+/// given to a [`Client`] (see [`RecordingTransport::client`]) and another kept for assertions.
+#[derive(Clone, Default)]
+pub struct RecordingTransport {
+    recording: Arc<Mutex<Recording>>,
+}
+
+#[derive(Default)]
+struct Recording {
+    requests: Vec<RecordedRequest>,
+    responses: VecDeque<CannedResponse>,
+}
+
+/// A request recorded by a [`RecordingTransport`].
+#[derive(Clone, Debug)]
+pub struct RecordedRequest {
+    pub method: Method,
+    pub uri: String,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+}
+
+impl RecordedRequest {
+    /// Returns the path of the URI, for example `/v2/jobs`.
+    pub fn path(&self) -> &str {
+        let after_scheme = self
+            .uri
+            .split_once("://")
+            .map_or(&*self.uri, |(_, rest)| rest);
+        let path = after_scheme
+            .find('/')
+            .map_or("/", |idx| &after_scheme[idx..]);
+        path.split(['?', '#']).next().unwrap()
+    }
+
+    /// Returns the value of a header, if it's set and valid UTF-8.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name)?.to_str().ok()
+    }
+
+    /// Parse the body as JSON.
+    pub fn json(&self) -> serde_json::Result<Value> {
+        serde_json::from_slice(&self.body)
+    }
+}
+
+/// A response returned by a [`RecordingTransport`].
+#[derive(Clone, Debug)]
+pub struct CannedResponse {
+    pub status: StatusCode,
+    pub content_type: Option<&'static str>,
+    pub body: Vec<u8>,
+}
+
+impl CannedResponse {
+    pub fn new(status: StatusCode, body: impl Into<Vec<u8>>) -> CannedResponse {
+        CannedResponse {
+            status,
+            content_type: None,
+            body: body.into(),
+        }
+    }
+
+    /// A response with a JSON body.
+    pub fn json(status: StatusCode, body: &Value) -> CannedResponse {
+        CannedResponse {
+            status,
+            content_type: Some("application/json"),
+            body: serde_json::to_vec(body).unwrap(),
+        }
+    }
+}
+
+impl RecordingTransport {
+    pub fn new() -> RecordingTransport {
+        RecordingTransport::default()
+    }
+
+    /// Create a client using this transport, with the default CloudConvert endpoint and a dummy
+    /// bearer token.
+    pub fn client(&self) -> RecordingClient {
+        Client {
+            client: hapic::Client {
+                transport: self.clone(),
+                phantom_body: PhantomData,
+                endpoint: "https://api.cloudconvert.com/v2".into(),
+                authorization: Some("Bearer test-token".try_into().unwrap()),
+                extra_headers: HeaderMap::new(),
+            },
+        }
+    }
+
+    /// Queue a response, to be returned after the previously queued responses.
+    pub fn push_response(&self, response: CannedResponse) {
+        let mut recording = self.recording.lock().unwrap();
+        recording.responses.push_back(response);
+    }
+
+    /// Queue a JSON response, to be returned after the previously queued responses.
+    pub fn push_json(&self, status: StatusCode, body: Value) {
+        self.push_response(CannedResponse::json(status, &body));
+    }
+
+    /// Returns all the recorded requests, in the order they were made.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.recording.lock().unwrap().requests.clone()
+    }
+
+    /// Returns the most recent request.
+    pub fn last_request(&self) -> Option<RecordedRequest> {
+        self.recording.lock().unwrap().requests.last().cloned()
+    }
+
+    /// Forget the recorded requests and any queued responses.
+    pub fn clear(&self) {
+        let mut recording = self.recording.lock().unwrap();
+        recording.requests.clear();
+        recording.responses.clear();
+    }
+
+    /// Assert that exactly `count` requests have been made.
+    #[track_caller]
+    pub fn assert_request_count(&self, count: usize) {
+        let requests = self.requests();
+        assert_eq!(
+            requests.len(),
+            count,
+            "expected {count} requests, but {} were made: {:#?}",
+            requests.len(),
+            requests
+                .iter()
+                .map(|request| format!("{} {}", request.method, request.uri))
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    /// Assert that the request at `idx` was made with `method` to `path`, and return it.
+    #[track_caller]
+    pub fn assert_request(&self, idx: usize, method: Method, path: &str) -> RecordedRequest {
+        let requests = self.requests();
+        let Some(request) = requests.get(idx) else {
+            panic!(
+                "expected request {idx}, but only {} were made",
+                requests.len()
+            );
+        };
+        assert_eq!(
+            (&request.method, request.path()),
+            (&method, path),
+            "request {idx} didn't match"
+        );
+        request.clone()
+    }
+
+    /// Assert that the body of the request at `idx` is the JSON value `expected`.
+    #[track_caller]
+    pub fn assert_json_body(&self, idx: usize, expected: &Value) {
+        let requests = self.requests();
+        let Some(request) = requests.get(idx) else {
+            panic!(
+                "expected request {idx}, but only {} were made",
+                requests.len()
+            );
+        };
+        let body = request
+            .json()
+            .unwrap_or_else(|err| panic!("body of request {idx} isn't JSON: {err}"));
+        assert_eq!(&body, expected, "body of request {idx} didn't match");
+    }
+}
+
+#[async_trait::async_trait]
+impl hapic::transport::Transport<Vec<u8>> for RecordingTransport {
+    type Error = hapic::Error;
+    type ResponseBody = hyper::Body;
+
+    async fn request(&self, req: Request<Vec<u8>>) -> Result<Response<hyper::Body>, hapic::Error> {
+        let (parts, body) = req.into_parts();
+        let request = RecordedRequest {
+            method: parts.method,
+            uri: parts.uri.to_string(),
+            headers: parts.headers,
+            body,
+        };
+        let response = {
+            let mut recording = self.recording.lock().unwrap();
+            let response = recording
+                .responses
+                .pop_front()
+                .unwrap_or_else(|| dry_run_response(&request));
+            recording.requests.push(request);
+            response
+        };
+
+        let mut builder = Response::builder().status(response.status);
+        if let Some(content_type) = response.content_type {
+            builder = builder.header(hapic::http::header::CONTENT_TYPE, content_type);
+        }
+        Ok(builder.body(hyper::Body::from(response.body)).unwrap())
+    }
+}
+
+/// Build the response to a request which doesn't have a canned response.
+fn dry_run_response(request: &RecordedRequest) -> CannedResponse {
+    let body = request.json().unwrap_or(Value::Null);
+    let path = request.path();
+    let operation = path
+        .strip_prefix("/v2/")
+        .unwrap_or(path.trim_start_matches('/'));
+    let task = |id: String, job_id: Option<&str>, name: Option<&str>, operation: &str, payload| {
+        json!({
+            "id": id,
+            "job_id": job_id,
+            "name": name,
+            "operation": operation,
+            "status": "waiting",
+            "payload": payload,
+        })
+    };
+
+    match (&request.method, operation) {
+        (&Method::POST, "jobs") => {
+            let tasks: Vec<Value> = body
+                .get("tasks")
+                .and_then(Value::as_object)
+                .into_iter()
+                .flatten()
+                .map(|(name, payload)| {
+                    let operation = payload.get("operation").and_then(Value::as_str);
+                    task(
+                        format!("dry-run-task-{name}"),
+                        Some("dry-run-job"),
+                        Some(name),
+                        operation.unwrap_or_default(),
+                        payload.clone(),
+                    )
+                })
+                .collect();
+            CannedResponse::json(
+                StatusCode::CREATED,
+                &json!({
+                    "data": {
+                        "id": "dry-run-job",
+                        "tag": body.get("tag"),
+                        "status": "waiting",
+                        "tasks": tasks,
+                    },
+                }),
+            )
+        }
+        (&Method::POST, operation) if !operation.starts_with("jobs") => CannedResponse::json(
+            StatusCode::CREATED,
+            &json!({
+                "data": task("dry-run-task".to_string(), None, None, operation, body),
+            }),
+        ),
+        _ => CannedResponse::json(
+            StatusCode::NOT_FOUND,
+            &json!({
+                "message": format!("No canned response for {} {}", request.method, request.uri),
+                "code": "NOT_FOUND",
+            }),
+        ),
+    }
+}
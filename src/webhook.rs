@@ -4,7 +4,12 @@ use serde::Deserialize;
 
 use super::job::Job;
 
-#[derive(Debug, Deserialize, PartialEq, Eq)]
+/// The kind of an event.
+///
+/// Webhooks are only sent for job events. Task events are received using the `realtime` module,
+/// which requires the `realtime` feature.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum EventKind {
     #[serde(rename = "job.created")]
     JobCreated,
@@ -12,6 +17,14 @@ pub enum EventKind {
     JobFinished,
     #[serde(rename = "job.failed")]
     JobFailed,
+    #[serde(rename = "task.created")]
+    TaskCreated,
+    #[serde(rename = "task.updated")]
+    TaskUpdated,
+    #[serde(rename = "task.finished")]
+    TaskFinished,
+    #[serde(rename = "task.failed")]
+    TaskFailed,
 }
 
 /// A signed webhook event.